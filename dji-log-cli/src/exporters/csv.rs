@@ -157,8 +157,23 @@ fn get_headers(frame: &Frame) -> Vec<String> {
         "RECOVER.batterySerial".to_string(), // Serial number of the battery
         "APP.tip".to_string(),           // App tip
         "APP.warn".to_string(),          // App warning
-        "DETAILS.totalTime".to_string(), // Total flight time in seconds
-        "DETAILS.totalDistance".to_string(), // Total distance flown in meters
+        "DEFORM.isDeformProtected".to_string(), // Indicates if the landing gear is protected from deformation
+        "DEFORM.deformStatus".to_string(),      // Current state of the landing gear
+        "DEFORM.deformMode".to_string(),        // Current mode of the landing gear
+        "FLIGHT_CONTROLLER.failSafeProtection".to_string(), // Action executed when the remote controller signal is lost
+        "FLIGHT_CONTROLLER.isMvoEnabled".to_string(), // Indicates if the monocular visual odometry is enabled
+        "FLIGHT_CONTROLLER.isAvoidObstacleEnabled".to_string(), // Indicates if obstacle avoidance is enabled
+        "FLIGHT_CONTROLLER.isUserAvoidEnabled".to_string(), // Indicates if obstacle avoidance is enabled by the user
+        "VIRTUAL_STICK.verticalControlMode".to_string(), // Meaning of the throttle value, velocity or altitude
+        "VIRTUAL_STICK.rollPitchControlMode".to_string(), // Meaning of the roll and pitch values, angle or velocity
+        "VIRTUAL_STICK.yawControlMode".to_string(), // Meaning of the yaw value, angle or angular velocity
+        "VIRTUAL_STICK.coordinateSystem".to_string(), // Coordinate system of the roll and pitch values
+        "VIRTUAL_STICK.roll".to_string(),             // Roll input sent by the SDK application
+        "VIRTUAL_STICK.pitch".to_string(),            // Pitch input sent by the SDK application
+        "VIRTUAL_STICK.yaw".to_string(),              // Yaw input sent by the SDK application
+        "VIRTUAL_STICK.throttle".to_string(),         // Throttle input sent by the SDK application
+        "DETAILS.totalTime".to_string(),              // Total flight time in seconds
+        "DETAILS.totalDistance".to_string(),          // Total distance flown in meters
         "DETAILS.maxHeight".to_string(), // Maximum height reached during the flight in meters
         "DETAILS.maxHorizontalSpeed".to_string(), // Maximum horizontal speed reached during the flight in meters per second
         "DETAILS.maxVerticalSpeed".to_string(), // Maximum vertical speed reached during the flight in meters per second
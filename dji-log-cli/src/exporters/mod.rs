@@ -9,3 +9,6 @@ pub use geojson::GeoJsonExporter;
 pub use image::ImageExporter;
 pub use json::JsonExporter;
 pub use kml::KmlExporter;
+
+#[cfg(test)]
+mod tests;
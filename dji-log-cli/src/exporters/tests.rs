@@ -0,0 +1,208 @@
+use clap::Parser;
+use dji_log_fixtures::{FlightScript, VERSIONS};
+use dji_log_parser::frame::Frame;
+use dji_log_parser::layout::details::ProductType;
+use dji_log_parser::record::Record;
+use dji_log_parser::DJILog;
+use geojson::GeoJson;
+use kml::Kml;
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+
+use super::{CSVExporter, GeoJsonExporter, ImageExporter, JsonExporter, KmlExporter};
+use crate::{Cli, Exporter};
+
+/// Minimal JPEG image: a JFIF segment and two comment segments between the start and end of
+/// image markers. EXIF metadata is inserted after the third segment
+const JPEG: [u8; 34] = [
+    0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x10, b'J', b'F', b'I', b'F', 0x00, 0x01, 0x01, 0x00, 0x00, 0x01,
+    0x00, 0x01, 0x00, 0x00, 0xFF, 0xFE, 0x00, 0x04, b'D', b'J', 0xFF, 0xFE, 0x00, 0x04, b'I', b'!',
+    0xFF, 0xD9,
+];
+
+struct Flight {
+    parser: DJILog,
+    records: Vec<Record>,
+    frames: Vec<Frame>,
+}
+
+fn flight(version: u8) -> Flight {
+    let bytes = FlightScript::new(ProductType::Mavic2, 46.7712, 8.6345)
+        .takeoff(30.0)
+        .leg(90.0, 200.0, 8.0)
+        .warning("Strong wind. Fly with caution.")
+        .return_to_home(10.0)
+        .land()
+        .write(version)
+        .unwrap();
+    let parser = DJILog::from_bytes(bytes).unwrap();
+
+    Flight {
+        records: parser.records(None).unwrap(),
+        frames: parser.frames(None).unwrap(),
+        parser,
+    }
+}
+
+/// Path of an output file, unique to the test process.
+fn output_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("dji-log-cli-{}-{}", std::process::id(), name))
+}
+
+fn export(exporter: &dyn Exporter, flight: &Flight, options: &[&str]) {
+    let args = Cli::parse_from(["dji-log", "DJIFlightRecord.txt"].iter().chain(options));
+    exporter.export(&flight.parser, &flight.records, &flight.frames, &args);
+}
+
+#[test]
+fn json_exporter_writes_frames() {
+    for version in VERSIONS {
+        let flight = flight(version);
+        let path = output_path(&format!("frames-{}.json", version));
+
+        export(
+            &JsonExporter,
+            &flight,
+            &["--output", path.to_str().unwrap()],
+        );
+        let json: Value = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(json["version"], version);
+        assert_eq!(json["details"]["aircraftSn"], "0A1B2C3D4E");
+        assert_eq!(
+            json["frames"].as_array().unwrap().len(),
+            flight.frames.len(),
+            "version {}",
+            version
+        );
+    }
+}
+
+#[test]
+fn json_exporter_writes_raw_records() {
+    let flight = flight(12);
+    let path = output_path("records.json");
+
+    export(
+        &JsonExporter,
+        &flight,
+        &["--raw", "--output", path.to_str().unwrap()],
+    );
+    let json: Value = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    let records = json["records"].as_array().unwrap();
+    assert_eq!(records.len(), flight.records.len());
+    assert!(records.iter().any(|record| record["type"] == "AppWarn"
+        && record["content"]["message"] == "Strong wind. Fly with caution."));
+}
+
+#[test]
+fn csv_exporter_writes_one_row_per_frame() {
+    for version in VERSIONS {
+        let flight = flight(version);
+        let path = output_path(&format!("frames-{}.csv", version));
+
+        export(&CSVExporter, &flight, &["--csv", path.to_str().unwrap()]);
+        let mut reader = csv::Reader::from_path(&path).unwrap();
+        let headers = reader.headers().unwrap().clone();
+        let rows: Vec<_> = reader.records().map(Result::unwrap).collect();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(rows.len(), flight.frames.len(), "version {}", version);
+
+        let latitude = headers.iter().position(|h| h == "OSD.latitude").unwrap();
+        let warn = headers.iter().position(|h| h == "APP.warn").unwrap();
+        let aircraft_sn = headers
+            .iter()
+            .position(|h| h == "DETAILS.aircraftSerial")
+            .unwrap();
+        for (row, frame) in rows.iter().zip(&flight.frames) {
+            // Every value has a header
+            assert_eq!(row.len(), headers.len(), "version {}", version);
+            assert_eq!(row[latitude].parse::<f64>().unwrap(), frame.osd.latitude);
+            assert_eq!(&row[warn], frame.app.warn);
+            assert_eq!(&row[aircraft_sn], "0A1B2C3D4E");
+        }
+    }
+}
+
+#[test]
+fn geojson_exporter_writes_flight_track() {
+    let flight = flight(12);
+    let path = output_path("track.geojson");
+
+    export(
+        &GeoJsonExporter,
+        &flight,
+        &["--geojson", path.to_str().unwrap()],
+    );
+    let geojson: GeoJson = fs::read_to_string(&path).unwrap().parse().unwrap();
+    fs::remove_file(&path).unwrap();
+
+    let GeoJson::Feature(feature) = geojson else {
+        panic!("GeoJSON is not a feature");
+    };
+    let Some(geojson::Value::LineString(coords)) = feature.geometry.map(|g| g.value) else {
+        panic!("GeoJSON feature is not a line string");
+    };
+    assert_eq!(coords.len(), flight.frames.len());
+    assert_eq!(coords[0][0], flight.frames[0].osd.longitude);
+    assert_eq!(coords[0][1], flight.frames[0].osd.latitude);
+}
+
+#[test]
+fn kml_exporter_writes_flight_track() {
+    let flight = flight(12);
+    let path = output_path("track.kml");
+
+    export(&KmlExporter, &flight, &["--kml", path.to_str().unwrap()]);
+    let kml: Kml = fs::read_to_string(&path).unwrap().parse().unwrap();
+    fs::remove_file(&path).unwrap();
+
+    let Kml::KmlDocument(document) = kml else {
+        panic!("KML is not a document");
+    };
+    let Some(Kml::Placemark(placemark)) = document.elements.first() else {
+        panic!("KML document has no placemark");
+    };
+    let Some(kml::types::Geometry::LineString(line)) = &placemark.geometry else {
+        panic!("KML placemark is not a line string");
+    };
+    assert_eq!(placemark.name.as_deref(), Some("Mavic2"));
+    assert_eq!(line.coords.len(), flight.frames.len());
+}
+
+#[test]
+fn image_exporter_writes_images_with_exif() {
+    let mut flight = flight(12);
+    flight.parser.details.moment_pic_image_buffer_len[0] = JPEG.len() as i32;
+    flight.parser.details.moment_pic_shrink_image_buffer_len[0] = JPEG.len() as i32;
+    flight.records.push(Record::JPEG(JPEG.to_vec()));
+
+    let images = output_path("image%d.jpeg");
+    let thumbnails = output_path("thumb%d.jpeg");
+    export(
+        &ImageExporter,
+        &flight,
+        &[
+            "--images",
+            images.to_str().unwrap(),
+            "--thumbnails",
+            thumbnails.to_str().unwrap(),
+        ],
+    );
+
+    for path in [output_path("image1.jpeg"), output_path("thumb1.jpeg")] {
+        let image = fs::read(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(image[..2], [0xFF, 0xD8]);
+        // EXIF segment with the aircraft make
+        assert!(image.windows(4).any(|window| window == b"Exif"));
+        assert!(image.windows(3).any(|window| window == b"DJI"));
+    }
+    assert!(!output_path("image2.jpeg").exists());
+}
@@ -1,5 +1,6 @@
 use clap::Parser;
 use dji_log_parser::frame::Frame;
+use dji_log_parser::keychain::{KeychainClient, KeychainFeaturePoint};
 use dji_log_parser::layout::auxiliary::Department;
 use dji_log_parser::record::Record;
 use dji_log_parser::DJILog;
@@ -55,6 +56,30 @@ pub(crate) struct Cli {
     /// Custom version for keychain request
     #[arg(long)]
     api_custom_version: Option<u16>,
+
+    /// Load keychains from a JSON FILE instead of fetching them with an Api Key
+    #[arg(long, value_name = "FILE")]
+    keychains: Option<String>,
+
+    /// Save the keychains used to decode the log to a JSON FILE
+    #[arg(long, value_name = "FILE")]
+    save_keychains: Option<String>,
+
+    /// Save the keychain request body to a JSON FILE
+    #[arg(long, value_name = "FILE")]
+    save_keychains_request: Option<String>,
+
+    /// Save a decrypted copy of the log to FILE. Copies keeping version 13 and above are read with an empty keychains file
+    #[arg(long, value_name = "FILE")]
+    decrypted: Option<String>,
+
+    /// Check the log was not altered and save the integrity report to a JSON FILE
+    #[arg(long, value_name = "FILE")]
+    integrity: Option<String>,
+
+    /// Save the summary of the faults reported by the aircraft to a JSON FILE
+    #[arg(long, value_name = "FILE")]
+    fault_summary: Option<String>,
 }
 
 pub(crate) trait Exporter {
@@ -68,27 +93,81 @@ fn main() {
     let parser = DJILog::from_bytes(bytes).expect("Unable to parse file");
 
     let keychains = if parser.version >= 13 {
-        match &args.api_key {
-            Some(api_key) => {
-                let department = args.api_custom_department.map(Department::from);
-                let version = args.api_custom_version;
+        // The request is only needed to fetch keychains or to be saved, not with a keychains file
+        let fetch = args.keychains.is_none() && args.api_key.is_some();
+        let req = if fetch || args.save_keychains_request.is_some() {
+            let department = args.api_custom_department.map(Department::from);
+            let version = args.api_custom_version;
+
+            let (req, report) = parser
+                .keychains_request_with_report(department, version)
+                .expect("Unable to create keychain request");
+
+            if !report.is_clean() {
+                eprintln!(
+                    "Keychain request may be incomplete, records decoding stopped: {:?}",
+                    report.issues
+                );
+            }
 
-                let req = parser
-                    .keychains_request_with_custom_params(department, version)
-                    .expect("Unable to create keychain request");
+            if let Some(path) = &args.save_keychains_request {
+                let json =
+                    serde_json::to_string(&req).expect("Unable to serialize keychain request");
+                fs::write(path, json).expect("Unable to write keychain request file");
+            }
 
-                let keychains = req.fetch(api_key, None).expect("Unable to fetch keychain");
+            Some(req)
+        } else {
+            None
+        };
 
-                Some(keychains)
+        let keychains: Vec<Vec<KeychainFeaturePoint>> = match (&args.keychains, &args.api_key, req)
+        {
+            (Some(path), _, _) => {
+                let json = fs::read(path).expect("Unable to read keychains file");
+                serde_json::from_slice(&json).expect("Unable to parse keychains file")
             }
-            None => {
-                panic!("API Key is required for version 13 and above");
+            (None, Some(api_key), Some(req)) => KeychainClient::new(api_key)
+                .fetch(&req)
+                .expect("Unable to fetch keychain"),
+            _ => {
+                panic!("API Key or keychains file is required for version 13 and above");
             }
+        };
+
+        if let Some(path) = &args.save_keychains {
+            let json = serde_json::to_string(&keychains).expect("Unable to serialize keychains");
+            fs::write(path, json).expect("Unable to write keychains file");
         }
+
+        Some(keychains)
     } else {
         None
     };
 
+    if let Some(path) = &args.decrypted {
+        let bytes = parser
+            .decrypt(keychains.clone())
+            .expect("Unable to decrypt log");
+        fs::write(path, bytes).expect("Unable to write decrypted log file");
+    }
+
+    if let Some(path) = &args.integrity {
+        let report = parser
+            .verify_integrity(keychains.clone())
+            .expect("Unable to verify log integrity");
+        let json = serde_json::to_string(&report).expect("Unable to serialize integrity report");
+        fs::write(path, json).expect("Unable to write integrity report file");
+    }
+
+    if let Some(path) = &args.fault_summary {
+        let summary = parser
+            .fault_summary(keychains.clone())
+            .expect("Unable to read fault summary");
+        let json = serde_json::to_string(&summary).expect("Unable to serialize fault summary");
+        fs::write(path, json).expect("Unable to write fault summary file");
+    }
+
     let records = parser
         .records(keychains.clone())
         .expect("Unable to parse records");
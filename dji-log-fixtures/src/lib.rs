@@ -0,0 +1,743 @@
+//! # DJILog Fixtures
+//!
+//! Generates synthetic DJI logs from a scripted flight, to exercise `dji-log-parser` without
+//! relying on real flight logs.
+//!
+//! A flight is described as a timeline of steps (takeoff, legs, warnings, return to home,
+//! landing) and simulated at a fixed sample rate. The simulation is converted to a `Record`
+//! sequence and written with `DJILogWriter` for each format version from 1 to 12, which gives
+//! fixtures for `records_to_frames`, the exporters and the version specific layouts of `Details`.
+//!
+//! Logs version 13 and above are written with records AES encrypted with generated keychains,
+//! returned along with the log, see `FlightScript::write_encrypted`.
+//!
+//! ## Usage
+//!
+//! ```
+//! # use dji_log_fixtures::{FlightScript, VERSIONS};
+//! # use dji_log_parser::layout::details::ProductType;
+//! let script = FlightScript::new(ProductType::Mavic2, 46.7712, 8.6345)
+//!     .takeoff(30.0)
+//!     .leg(90.0, 200.0, 8.0)
+//!     .warning("Strong wind. Fly with caution.")
+//!     .leg(0.0, 150.0, 8.0)
+//!     .return_to_home(10.0)
+//!     .land();
+//!
+//! for version in VERSIONS {
+//!     let bytes = script.write(version)?;
+//! }
+//! # Ok::<(), dji_log_parser::Error>(())
+//! ```
+//!
+use aes::cipher::block_padding::Pkcs7;
+use aes::cipher::{BlockEncryptMut, KeyIvInit};
+use aes::Aes256;
+use base64::engine::general_purpose::STANDARD as Base64Standard;
+use base64::Engine as _;
+use chrono::{DateTime, Datelike, Duration, Timelike, Utc};
+use std::io::Cursor;
+use std::ops::RangeInclusive;
+
+use dji_log_parser::keychain::{FeaturePoint, KeychainFeaturePoint};
+use dji_log_parser::layout::auxiliary::{AuxiliaryVersion, Department};
+use dji_log_parser::layout::details::{Details, Platform, ProductType};
+use dji_log_parser::record::app_gps::AppGPS;
+use dji_log_parser::record::app_tip::AppTip;
+use dji_log_parser::record::app_warn::AppWarn;
+use dji_log_parser::record::center_battery::CenterBattery;
+use dji_log_parser::record::custom::Custom;
+use dji_log_parser::record::gimbal::{Gimbal, GimbalMode};
+use dji_log_parser::record::home::{GoHomeMode, Home};
+use dji_log_parser::record::key_storage::KeyStorage;
+use dji_log_parser::record::osd::{
+    BatteryType, FlightAction, FlightMode, GoHomeStatus, GroundOrSky, OSD,
+};
+use dji_log_parser::record::rc::RC;
+use dji_log_parser::record::rc_gps::RCGPS;
+use dji_log_parser::record::recover::Recover;
+use dji_log_parser::record::Record;
+use dji_log_parser::{DJILogWriter, Result};
+
+/// Log format versions that can be generated.
+pub const VERSIONS: RangeInclusive<u8> = 1..=12;
+
+type Aes256CbcEnc = cbc::Encryptor<Aes256>;
+
+/// Mean earth radius in meters
+const EARTH_RADIUS: f64 = 6_371_000.0;
+
+/// Stick values of the remote controller
+const STICK_CENTER: u16 = 1024;
+const STICK_RANGE: f32 = 660.0;
+
+/// Climb and descent speeds in meters / sec
+const TAKEOFF_SPEED: f32 = 3.0;
+const LANDING_SPEED: f32 = 1.5;
+
+/// Time spent on ground with motors off before takeoff and after landing, in seconds
+const GROUND_IDLE: f64 = 2.0;
+
+/// Capacity of the generated battery in mAh
+const BATTERY_CAPACITY: u16 = 3850;
+
+/// Serial numbers, short enough to fit the layout of all versions
+const AIRCRAFT_SN: &str = "0A1B2C3D4E";
+const CAMERA_SN: &str = "1B2C3D4E5F";
+const RC_SN: &str = "2C3D4E5F6G";
+const BATTERY_SN: &str = "3D4E5F6G7H";
+
+#[derive(Debug, Clone)]
+enum Step {
+    Takeoff {
+        altitude: f32,
+    },
+    Leg {
+        bearing: f64,
+        distance: f64,
+        speed: f32,
+    },
+    Hover {
+        duration: f64,
+    },
+    ReturnToHome {
+        speed: f32,
+    },
+    Land,
+    Tip(String),
+    Warning(String),
+}
+
+#[derive(Debug, Clone)]
+enum Message {
+    Tip(String),
+    Warning(String),
+}
+
+/// State of the aircraft at a sample of the simulation.
+#[derive(Debug, Clone, Default)]
+struct Sample {
+    /// seconds since the start of the log
+    time: f64,
+    /// degrees
+    latitude: f64,
+    /// degrees
+    longitude: f64,
+    /// meters, relative to the takeoff point
+    height: f32,
+    /// meters / sec, north
+    speed_x: f32,
+    /// meters / sec, east
+    speed_y: f32,
+    /// meters / sec, down
+    speed_z: f32,
+    /// degrees
+    yaw: f32,
+    /// percent
+    battery: f32,
+    /// meters
+    distance: f64,
+    /// seconds with motors on
+    fly_time: f64,
+    flight_mode: FlightMode,
+    flight_action: FlightAction,
+    go_home_status: GoHomeStatus,
+    is_motor_up: bool,
+    messages: Vec<Message>,
+}
+
+/// Scripted flight, converted to DJI log records.
+///
+/// Steps are played in order from the home point. Each step starts where the previous one
+/// ended. The aircraft idles on ground with motors off before the takeoff and after the landing.
+///
+#[derive(Debug, Clone)]
+pub struct FlightScript {
+    product_type: ProductType,
+    latitude: f64,
+    longitude: f64,
+    start_time: DateTime<Utc>,
+    sample_rate: u32,
+    battery_drain: f32,
+    steps: Vec<Step>,
+}
+
+impl FlightScript {
+    /// Creates an empty script.
+    ///
+    /// # Arguments
+    ///
+    /// * `product_type` - The aircraft model.
+    /// * `latitude` - Latitude of the home point in degrees.
+    /// * `longitude` - Longitude of the home point in degrees.
+    pub fn new(product_type: ProductType, latitude: f64, longitude: f64) -> Self {
+        FlightScript {
+            product_type,
+            latitude,
+            longitude,
+            start_time: DateTime::from_timestamp(1_700_000_000, 0).unwrap_or_default(),
+            sample_rate: 10,
+            battery_drain: 3.0,
+            steps: Vec::new(),
+        }
+    }
+
+    /// Sets the start time of the log. Defaults to 2023-11-14 22:13:20 UTC.
+    pub fn start_time(mut self, start_time: DateTime<Utc>) -> Self {
+        self.start_time = start_time;
+        self
+    }
+
+    /// Sets the number of OSD samples per second. Defaults to 10.
+    ///
+    /// Home, battery and GPS records are written once per second whatever the sample rate.
+    pub fn sample_rate(mut self, sample_rate: u32) -> Self {
+        self.sample_rate = sample_rate.max(1);
+        self
+    }
+
+    /// Sets the battery drain in percent per minute of flight. Defaults to 3.
+    pub fn battery_drain(mut self, battery_drain: f32) -> Self {
+        self.battery_drain = battery_drain;
+        self
+    }
+
+    /// Starts the motors and climbs vertically.
+    ///
+    /// # Arguments
+    ///
+    /// * `altitude` - Height above the home point to reach, in meters.
+    pub fn takeoff(mut self, altitude: f32) -> Self {
+        self.steps.push(Step::Takeoff { altitude });
+        self
+    }
+
+    /// Flies a straight line at constant height.
+    ///
+    /// # Arguments
+    ///
+    /// * `bearing` - Direction in degrees, clockwise from north.
+    /// * `distance` - Length of the leg in meters.
+    /// * `speed` - Horizontal speed in meters / sec.
+    pub fn leg(mut self, bearing: f64, distance: f64, speed: f32) -> Self {
+        self.steps.push(Step::Leg {
+            bearing,
+            distance,
+            speed,
+        });
+        self
+    }
+
+    /// Hovers in place.
+    ///
+    /// # Arguments
+    ///
+    /// * `duration` - Hover duration in seconds.
+    pub fn hover(mut self, duration: f64) -> Self {
+        self.steps.push(Step::Hover { duration });
+        self
+    }
+
+    /// Flies back over the home point in GoHome mode.
+    ///
+    /// # Arguments
+    ///
+    /// * `speed` - Horizontal speed in meters / sec.
+    pub fn return_to_home(mut self, speed: f32) -> Self {
+        self.steps.push(Step::ReturnToHome { speed });
+        self
+    }
+
+    /// Descends vertically and stops the motors.
+    pub fn land(mut self) -> Self {
+        self.steps.push(Step::Land);
+        self
+    }
+
+    /// Emits an app tip at the current time of the timeline.
+    pub fn tip(mut self, message: &str) -> Self {
+        self.steps.push(Step::Tip(message.to_owned()));
+        self
+    }
+
+    /// Emits an app warning at the current time of the timeline.
+    pub fn warning(mut self, message: &str) -> Self {
+        self.steps.push(Step::Warning(message.to_owned()));
+        self
+    }
+
+    /// Builds the details of the log, summarizing the simulated flight.
+    pub fn details(&self) -> Details {
+        let samples = self.simulate();
+        let last = samples.last().cloned().unwrap_or_default();
+
+        Details {
+            start_time: self.start_time,
+            latitude: self.latitude,
+            longitude: self.longitude,
+            total_distance: last.distance as f32,
+            total_time: last.time,
+            max_height: samples.iter().map(|s| s.height).fold(0.0, f32::max),
+            max_horizontal_speed: samples
+                .iter()
+                .map(|s| s.speed_x.hypot(s.speed_y))
+                .fold(0.0, f32::max),
+            max_vertical_speed: samples.iter().map(|s| s.speed_z.abs()).fold(0.0, f32::max),
+            record_line_count: samples.len() as i32,
+            product_type: self.product_type,
+            aircraft_name: format!("{:?}", self.product_type),
+            aircraft_sn: AIRCRAFT_SN.to_owned(),
+            camera_sn: CAMERA_SN.to_owned(),
+            rc_sn: RC_SN.to_owned(),
+            battery_sn: BATTERY_SN.to_owned(),
+            app_platform: Platform::Android,
+            app_version: "4.3.0".to_owned(),
+            ..Details::default()
+        }
+    }
+
+    /// Builds the records of the simulated flight.
+    ///
+    /// Fields missing from the record layouts of `version` are left to their default value, so
+    /// records match what the parser reads back from a log of that version.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - The log format version, from 1 to 12.
+    pub fn records(&self, version: u8) -> Vec<Record> {
+        let samples = self.simulate();
+        let mut records = Vec::with_capacity(samples.len() * 5);
+
+        records.push(Record::Recover(Recover {
+            product_type: self.product_type,
+            app_platform: Platform::Android,
+            app_version: "4.3.0".to_owned(),
+            aircraft_sn: AIRCRAFT_SN.to_owned(),
+            aircraft_name: format!("{:?}", self.product_type),
+            timestamp: self.start_time,
+            camera_sn: CAMERA_SN.to_owned(),
+            rc_sn: RC_SN.to_owned(),
+            battery_sn: BATTERY_SN.to_owned(),
+        }));
+
+        for (index, sample) in samples.iter().enumerate() {
+            // OSD records close the previous frame, other records of the sample follow
+            records.push(Record::OSD(self.osd(sample)));
+
+            if index % self.sample_rate as usize == 0 {
+                records.push(Record::Home(self.home(version)));
+                records.push(Record::CenterBattery(self.center_battery(sample, version)));
+                records.push(Record::AppGPS(AppGPS {
+                    longitude: self.longitude,
+                    latitude: self.latitude,
+                }));
+                records.push(Record::RCGPS(self.rc_gps(sample)));
+            }
+
+            records.push(Record::Gimbal(Gimbal {
+                pitch: if sample.is_motor_up { -30.0 } else { 0.0 },
+                yaw: sample.yaw,
+                mode: GimbalMode::YawFollow,
+                ..Gimbal::default()
+            }));
+            records.push(Record::RC(rc(sample)));
+            records.push(Record::Custom(Custom {
+                h_speed: sample.speed_x.hypot(sample.speed_y),
+                distance: sample.distance as f32,
+                update_timestamp: self.start_time
+                    + Duration::milliseconds((sample.time * 1000.0) as i64),
+            }));
+
+            for message in &sample.messages {
+                records.push(match message {
+                    Message::Tip(message) => Record::AppTip(AppTip {
+                        message: message.clone(),
+                    }),
+                    Message::Warning(message) => Record::AppWarn(AppWarn {
+                        message: message.clone(),
+                    }),
+                });
+            }
+        }
+
+        records
+    }
+
+    /// Writes the simulated flight as a DJI log.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - The log format version, from 1 to 12.
+    ///
+    /// # Returns
+    ///
+    /// The log bytes, or `Error::UnsupportedVersion` if `version` cannot be written.
+    pub fn write(&self, version: u8) -> Result<Vec<u8>> {
+        let mut writer = DJILogWriter::new(Cursor::new(Vec::new()), version, self.details())?;
+        for record in self.records(version) {
+            writer.write_record(&record)?;
+        }
+        Ok(writer.finish()?.into_inner())
+    }
+
+    /// Writes the simulated flight as a DJI log with AES encrypted records.
+    ///
+    /// Each feature point of the records gets a key and an IV of its own, and a `KeyStorage`
+    /// record with a dummy ciphertext is written before the flight records. The keychains are
+    /// returned instead of being fetched from the DJI API.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - The log format version, 13 or above.
+    ///
+    /// # Returns
+    ///
+    /// The log bytes and its keychains, or `Error::UnsupportedVersion` if `version` is below 13.
+    pub fn write_encrypted(
+        &self,
+        version: u8,
+    ) -> Result<(Vec<u8>, Vec<Vec<KeychainFeaturePoint>>)> {
+        let records = self.records(version);
+
+        // Key and current IV of each feature point, in order of first use
+        let mut ciphers: Vec<(FeaturePoint, [u8; 32], [u8; 16])> = Vec::new();
+        for record_type in records.iter().filter_map(Record::record_type) {
+            let feature_point = FeaturePoint::from_record_type(record_type, version);
+            if feature_point != FeaturePoint::PlaintextFeature
+                && !ciphers.iter().any(|(entry, _, _)| *entry == feature_point)
+            {
+                let seed = feature_point as u8;
+                ciphers.push((feature_point, [seed; 32], [seed.wrapping_mul(16); 16]));
+            }
+        }
+
+        let keychains = vec![ciphers
+            .iter()
+            .map(|(feature_point, key, iv)| KeychainFeaturePoint {
+                feature_point: *feature_point,
+                aes_key: Base64Standard.encode(key),
+                aes_iv: Base64Standard.encode(iv),
+            })
+            .collect()];
+
+        let mut writer = DJILogWriter::new_with_auxiliary(
+            Cursor::new(Vec::new()),
+            version,
+            self.details(),
+            AuxiliaryVersion {
+                version: 1,
+                department: Department::DJIFly,
+            },
+        )?;
+
+        for (feature_point, _, _) in &ciphers {
+            writer.write_record(&Record::KeyStorage(KeyStorage {
+                feature_point: *feature_point,
+                data: vec![*feature_point as u8; 32],
+            }))?;
+        }
+
+        for record in &records {
+            let Some(record_type) = record.record_type() else {
+                continue;
+            };
+            let content = record.content(version)?;
+            let feature_point = FeaturePoint::from_record_type(record_type, version);
+
+            match ciphers
+                .iter_mut()
+                .find(|(entry, _, _)| *entry == feature_point)
+            {
+                Some((_, key, iv)) => {
+                    let ciphertext = encrypt(&content, key, iv);
+                    // The last block of a record is the IV of the next one
+                    iv.copy_from_slice(&ciphertext[ciphertext.len() - 16..]);
+                    writer.write_raw_record(record_type, &ciphertext)?;
+                }
+                None => writer.write_raw_record(record_type, &content)?,
+            }
+        }
+
+        Ok((writer.finish()?.into_inner(), keychains))
+    }
+
+    fn osd(&self, sample: &Sample) -> OSD {
+        OSD {
+            longitude: sample.longitude,
+            latitude: sample.latitude,
+            altitude: sample.height,
+            speed_x: sample.speed_x,
+            speed_y: sample.speed_y,
+            speed_z: sample.speed_z,
+            yaw: sample.yaw,
+            flight_mode: sample.flight_mode,
+            ground_or_sky: if sample.height > 0.0 {
+                GroundOrSky::Sky
+            } else {
+                GroundOrSky::Ground
+            },
+            is_motor_up: sample.is_motor_up,
+            go_home_status: sample.go_home_status,
+            is_gps_valid: true,
+            gps_level: 5,
+            gps_num: 18,
+            battery_type: BatteryType::Smart,
+            flight_action: sample.flight_action,
+            battery: sample.battery.round() as u8,
+            fly_time: sample.fly_time as f32,
+            ..OSD::default()
+        }
+    }
+
+    fn home(&self, version: u8) -> Home {
+        Home {
+            longitude: self.longitude,
+            latitude: self.latitude,
+            is_home_record: true,
+            go_home_mode: GoHomeMode::Normal,
+            go_home_height: 30,
+            max_allowed_height: if version >= 8 { 120.0 } else { 0.0 },
+            ..Home::default()
+        }
+    }
+
+    fn center_battery(&self, sample: &Sample, version: u8) -> CenterBattery {
+        let cell_voltage = 3.5 + 0.85 * sample.battery / 100.0;
+        let cell_num = self.product_type.battery_cell_num().max(1);
+        let cell = |index: u8| if index < cell_num { cell_voltage } else { 0.0 };
+
+        CenterBattery {
+            relative_capacity: sample.battery.round() as u8,
+            voltage: cell_voltage * cell_num as f32,
+            current_capacity: (BATTERY_CAPACITY as f32 * sample.battery / 100.0) as u16,
+            full_capacity: BATTERY_CAPACITY,
+            life: 100,
+            current: if sample.is_motor_up { -8.5 } else { 0.0 },
+            voltage_cell1: cell(0),
+            voltage_cell2: cell(1),
+            voltage_cell3: cell(2),
+            voltage_cell4: cell(3),
+            voltage_cell5: cell(4),
+            voltage_cell6: cell(5),
+            temperature: if version >= 8 { 35.0 } else { 0.0 },
+            ..CenterBattery::default()
+        }
+    }
+
+    fn rc_gps(&self, sample: &Sample) -> RCGPS {
+        let time = self.start_time + Duration::milliseconds((sample.time * 1000.0) as i64);
+        RCGPS {
+            hour: time.hour() as u8,
+            minute: time.minute() as u8,
+            second: time.second() as u8,
+            year: time.year() as u16,
+            month: time.month() as u8,
+            day: time.day() as u8,
+            latitude: (self.latitude * 10_000_000.0).round() as i32,
+            longitude: (self.longitude * 10_000_000.0).round() as i32,
+            gps_num: 12,
+            accuracy: 3.0,
+            valid_data: 1,
+            ..RCGPS::default()
+        }
+    }
+
+    /// Plays the steps of the script.
+    fn simulate(&self) -> Vec<Sample> {
+        let mut simulation = Simulation {
+            dt: 1.0 / self.sample_rate as f64,
+            battery_drain: self.battery_drain,
+            samples: Vec::new(),
+            state: Sample {
+                latitude: self.latitude,
+                longitude: self.longitude,
+                battery: 100.0,
+                flight_mode: FlightMode::GPSAtti,
+                ..Sample::default()
+            },
+        };
+
+        simulation.idle(GROUND_IDLE);
+
+        for step in &self.steps {
+            match step {
+                Step::Takeoff { altitude } => {
+                    simulation.state.is_motor_up = true;
+                    simulation.state.flight_mode = FlightMode::AutoTakeoff;
+                    simulation.state.flight_action = FlightAction::AppAutoTakeoff;
+                    simulation.climb_to(*altitude, TAKEOFF_SPEED);
+                    simulation.state.flight_mode = FlightMode::GPSAtti;
+                    simulation.state.flight_action = FlightAction::None;
+                }
+                Step::Leg {
+                    bearing,
+                    distance,
+                    speed,
+                } => {
+                    let (latitude, longitude) = destination(
+                        simulation.state.latitude,
+                        simulation.state.longitude,
+                        *bearing,
+                        *distance,
+                    );
+                    simulation.fly_to(latitude, longitude, *speed);
+                }
+                Step::Hover { duration } => simulation.idle(*duration),
+                Step::ReturnToHome { speed } => {
+                    simulation.state.flight_mode = FlightMode::GoHome;
+                    simulation.state.flight_action = FlightAction::AppAutoGoHome;
+                    simulation.state.go_home_status = GoHomeStatus::Cruise;
+                    simulation.fly_to(self.latitude, self.longitude, *speed);
+                    simulation.state.go_home_status = GoHomeStatus::Standby;
+                }
+                Step::Land => {
+                    simulation.state.flight_mode = FlightMode::AutoLanding;
+                    simulation.climb_to(0.0, LANDING_SPEED);
+                    simulation.state.is_motor_up = false;
+                    simulation.state.flight_mode = FlightMode::GPSAtti;
+                    simulation.state.flight_action = FlightAction::None;
+                }
+                Step::Tip(message) => simulation
+                    .state
+                    .messages
+                    .push(Message::Tip(message.clone())),
+                Step::Warning(message) => simulation
+                    .state
+                    .messages
+                    .push(Message::Warning(message.clone())),
+            }
+        }
+
+        simulation.idle(GROUND_IDLE);
+        simulation.samples
+    }
+}
+
+struct Simulation {
+    /// seconds between two samples
+    dt: f64,
+    /// percent per minute
+    battery_drain: f32,
+    state: Sample,
+    samples: Vec<Sample>,
+}
+
+impl Simulation {
+    /// Records the current state and advances the clock by one sample.
+    fn tick(&mut self) {
+        self.samples.push(self.state.clone());
+        self.state.messages.clear();
+
+        self.state.time += self.dt;
+        if self.state.is_motor_up {
+            self.state.fly_time += self.dt;
+            self.state.battery =
+                (self.state.battery - self.battery_drain * self.dt as f32 / 60.0).max(0.0);
+        }
+    }
+
+    fn idle(&mut self, duration: f64) {
+        self.state.speed_x = 0.0;
+        self.state.speed_y = 0.0;
+        self.state.speed_z = 0.0;
+        for _ in 0..(duration / self.dt).round() as usize {
+            self.tick();
+        }
+    }
+
+    fn climb_to(&mut self, height: f32, speed: f32) {
+        let step = speed * self.dt as f32;
+        let count = ((height - self.state.height).abs() / step).ceil() as usize;
+        let direction = (height - self.state.height).signum();
+
+        self.state.speed_x = 0.0;
+        self.state.speed_y = 0.0;
+        // Vertical speed is positive downward
+        self.state.speed_z = -direction * speed;
+        for _ in 0..count {
+            self.tick();
+            self.state.height = if (height - self.state.height).abs() <= step {
+                height
+            } else {
+                self.state.height + direction * step
+            };
+        }
+        // Rounding errors of the steps may stop short of the target height
+        self.state.height = height;
+        self.state.speed_z = 0.0;
+    }
+
+    fn fly_to(&mut self, latitude: f64, longitude: f64, speed: f32) {
+        let (north, east) = offset(
+            self.state.latitude,
+            self.state.longitude,
+            latitude,
+            longitude,
+        );
+        let distance = north.hypot(east);
+        if distance == 0.0 || speed <= 0.0 {
+            return;
+        }
+
+        let count = (distance / (speed as f64 * self.dt)).ceil() as usize;
+        let (start_latitude, start_longitude) = (self.state.latitude, self.state.longitude);
+
+        self.state.yaw = east.atan2(north).to_degrees() as f32;
+        self.state.speed_x = (north / distance) as f32 * speed;
+        self.state.speed_y = (east / distance) as f32 * speed;
+        for index in 1..=count {
+            self.tick();
+            let ratio = index as f64 / count as f64;
+            self.state.latitude = start_latitude + (latitude - start_latitude) * ratio;
+            self.state.longitude = start_longitude + (longitude - start_longitude) * ratio;
+            self.state.distance += distance / count as f64;
+        }
+        self.state.speed_x = 0.0;
+        self.state.speed_y = 0.0;
+    }
+}
+
+/// Encrypts a record content with AES-256 in CBC mode and Pkcs7 padding.
+fn encrypt(content: &[u8], key: &[u8; 32], iv: &[u8; 16]) -> Vec<u8> {
+    let mut buffer = content.to_vec();
+    buffer.resize(content.len() + 16, 0);
+
+    Aes256CbcEnc::new(key.into(), iv.into())
+        .encrypt_padded_mut::<Pkcs7>(&mut buffer, content.len())
+        .map(|ciphertext| ciphertext.to_vec())
+        .unwrap_or_default()
+}
+
+/// Stick positions matching the motion of a sample.
+fn rc(sample: &Sample) -> RC {
+    let stick = |value: f32| (STICK_CENTER as f32 + value.clamp(-1.0, 1.0) * STICK_RANGE) as u16;
+    let horizontal_speed = sample.speed_x.hypot(sample.speed_y);
+
+    RC {
+        aileron: STICK_CENTER,
+        elevator: stick(horizontal_speed / 15.0),
+        throttle: stick(-sample.speed_z / 5.0),
+        rudder: STICK_CENTER,
+        gimbal: STICK_CENTER,
+        ..RC::default()
+    }
+}
+
+/// Coordinate reached from a point, using a local flat earth approximation.
+fn destination(latitude: f64, longitude: f64, bearing: f64, distance: f64) -> (f64, f64) {
+    let bearing = bearing.to_radians();
+    let north = distance * bearing.cos();
+    let east = distance * bearing.sin();
+
+    (
+        latitude + (north / EARTH_RADIUS).to_degrees(),
+        longitude + (east / (EARTH_RADIUS * latitude.to_radians().cos())).to_degrees(),
+    )
+}
+
+/// North and east offsets in meters between two points, using a local flat earth approximation.
+fn offset(latitude: f64, longitude: f64, to_latitude: f64, to_longitude: f64) -> (f64, f64) {
+    let north = (to_latitude - latitude).to_radians() * EARTH_RADIUS;
+    let east = (to_longitude - longitude).to_radians() * EARTH_RADIUS * latitude.to_radians().cos();
+    (north, east)
+}
@@ -0,0 +1,40 @@
+//! Writes a synthetic flight log for each format version from 1 to 12.
+//!
+//! Usage: `dji-log-fixtures [OUTPUT_DIR]`, files are named `v1.txt` to `v12.txt`.
+use std::path::PathBuf;
+use std::{env, fs, process};
+
+use dji_log_fixtures::{FlightScript, VERSIONS};
+use dji_log_parser::layout::details::ProductType;
+
+fn main() {
+    let output = PathBuf::from(env::args().nth(1).unwrap_or_else(|| ".".to_owned()));
+
+    let script = FlightScript::new(ProductType::Mavic2, 46.7712, 8.6345)
+        .takeoff(30.0)
+        .leg(90.0, 200.0, 8.0)
+        .warning("Strong wind. Fly with caution.")
+        .leg(0.0, 150.0, 8.0)
+        .hover(5.0)
+        .tip("Return to home started.")
+        .return_to_home(10.0)
+        .land();
+
+    if let Err(e) = fs::create_dir_all(&output) {
+        eprintln!("Unable to create {}: {}", output.display(), e);
+        process::exit(1);
+    }
+
+    for version in VERSIONS {
+        let path = output.join(format!("v{}.txt", version));
+        let result = script
+            .write(version)
+            .map_err(|e| e.to_string())
+            .and_then(|bytes| fs::write(&path, bytes).map_err(|e| e.to_string()));
+
+        if let Err(e) = result {
+            eprintln!("Unable to write {}: {}", path.display(), e);
+            process::exit(1);
+        }
+    }
+}
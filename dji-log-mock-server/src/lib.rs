@@ -0,0 +1,364 @@
+//! # DJILog Mock Server
+//!
+//! A local stand-in of the DJI keychain API (`/flight-records/keychains`), to exercise
+//! `KeychainsRequest::fetch`, `fetch_async` and the error mapping of `dji-log-parser`
+//! deterministically, without network access.
+//!
+//! The server answers keychain requests from a fixture map of AES ciphertexts to AES key and IV.
+//! HTTP failures can be scripted to simulate an invalid api key, a rate limit or a server outage,
+//! as well as API result codes and malformed response bodies.
+//!
+//! ## Usage
+//!
+//! ```
+//! use std::collections::HashMap;
+//!
+//! use dji_log_mock_server::{MockKeychain, MockKeychainServer};
+//! use dji_log_parser::keychain::{EncodedKeychainFeaturePoint, FeaturePoint, KeychainsRequest};
+//! use dji_log_parser::Error;
+//!
+//! let server = MockKeychainServer::start(HashMap::from([(
+//!     "__AES_CIPHERTEXT__".to_owned(),
+//!     MockKeychain::new("__AES_KEY__", "__AES_IV__"),
+//! )]))?;
+//!
+//! let request = KeychainsRequest {
+//!     version: 1,
+//!     department: 3,
+//!     keychains: vec![vec![EncodedKeychainFeaturePoint {
+//!         feature_point: FeaturePoint::BaseFeature,
+//!         aes_ciphertext: "__AES_CIPHERTEXT__".to_owned(),
+//!     }]],
+//! };
+//!
+//! // Fail the next request with a rate limit
+//! server.push_failure(429);
+//!
+//! let keychains = request.fetch("__DJI_API_KEY__", Some(&server.endpoint()));
+//! assert!(matches!(keychains, Err(Error::RateLimited)));
+//! # Ok::<(), std::io::Error>(())
+//! ```
+//!
+use std::collections::{HashMap, VecDeque};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+
+use dji_log_parser::keychain::{KeychainFeaturePoint, KeychainsRequest};
+use serde_json::json;
+
+/// Path of the keychain API served by the mock.
+pub const KEYCHAINS_PATH: &str = "/openapi/v1/flight-records/keychains";
+
+/// Result code returned by the mock when a ciphertext is not in the fixtures.
+///
+/// Any non-zero code is reported by the parser as `Error::ApiResponse` with an unknown code.
+pub const UNKNOWN_CIPHERTEXT_CODE: u8 = 1;
+
+/// AES key and IV returned for a ciphertext, both base64 encoded.
+#[derive(Debug, Clone)]
+pub struct MockKeychain {
+    pub aes_key: String,
+    pub aes_iv: String,
+}
+
+impl MockKeychain {
+    pub fn new(aes_key: &str, aes_iv: &str) -> Self {
+        MockKeychain {
+            aes_key: aes_key.to_owned(),
+            aes_iv: aes_iv.to_owned(),
+        }
+    }
+}
+
+/// Scripted answer to a request, instead of keychains.
+#[derive(Debug, Clone)]
+enum Failure {
+    /// HTTP status with an error body
+    Status(u16),
+    /// HTTP 200 with an API result code and message, without data
+    ResultCode(u8, String),
+    /// HTTP 200 with a body that is not a keychain response
+    MalformedBody,
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    keychains: HashMap<String, MockKeychain>,
+    api_key: Option<String>,
+    failures: VecDeque<Failure>,
+    requests: Vec<KeychainsRequest>,
+}
+
+/// Mock keychain API server listening on a local port.
+///
+/// The server runs on a background thread and is stopped when dropped.
+#[derive(Debug)]
+pub struct MockKeychainServer {
+    address: SocketAddr,
+    state: Arc<Mutex<MockState>>,
+    running: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MockKeychainServer {
+    /// Starts a server on a free local port.
+    ///
+    /// # Arguments
+    ///
+    /// * `keychains` - Fixture map of base64 AES ciphertexts to their AES key and IV.
+    ///
+    /// # Returns
+    ///
+    /// The running server, or an IO error if no local port could be bound.
+    pub fn start(keychains: HashMap<String, MockKeychain>) -> io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let address = listener.local_addr()?;
+
+        let state = Arc::new(Mutex::new(MockState {
+            keychains,
+            ..MockState::default()
+        }));
+        let running = Arc::new(AtomicBool::new(true));
+
+        let handle = {
+            let state = Arc::clone(&state);
+            let running = Arc::clone(&running);
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if !running.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    if let Ok(stream) = stream {
+                        // A broken connection only affects the client that opened it
+                        let _ = handle_connection(stream, &state);
+                    }
+                }
+            })
+        };
+
+        Ok(MockKeychainServer {
+            address,
+            state,
+            running,
+            handle: Some(handle),
+        })
+    }
+
+    /// Returns the keychain API endpoint of the server, to be passed to `KeychainsRequest::fetch`.
+    pub fn endpoint(&self) -> String {
+        format!("http://{}{}", self.address, KEYCHAINS_PATH)
+    }
+
+    /// Requires requests to carry the given api key. Other requests are answered with HTTP 403.
+    pub fn require_api_key(&self, api_key: &str) {
+        self.state().api_key = Some(api_key.to_owned());
+    }
+
+    /// Adds a keychain to the fixtures.
+    pub fn insert_keychain(&self, aes_ciphertext: &str, keychain: MockKeychain) {
+        self.state()
+            .keychains
+            .insert(aes_ciphertext.to_owned(), keychain);
+    }
+
+    /// Answers the next request with the given HTTP status instead of keychains.
+    ///
+    /// Failures are queued: pushing `429` then `503` fails the next two requests in that order.
+    pub fn push_failure(&self, status: u16) {
+        self.state().failures.push_back(Failure::Status(status));
+    }
+
+    /// Answers the next request with the given API result code and message instead of keychains.
+    ///
+    /// The response has an HTTP 200 status. Failures are queued with those of `push_failure`.
+    pub fn push_result_code(&self, code: u8, msg: &str) {
+        self.state()
+            .failures
+            .push_back(Failure::ResultCode(code, msg.to_owned()));
+    }
+
+    /// Answers the next request with an HTTP 200 status and a body that is not a keychain response.
+    ///
+    /// Failures are queued with those of `push_failure`.
+    pub fn push_malformed_body(&self) {
+        self.state().failures.push_back(Failure::MalformedBody);
+    }
+
+    /// Returns the keychain requests received so far, including failed ones.
+    pub fn requests(&self) -> Vec<KeychainsRequest> {
+        self.state().requests.clone()
+    }
+
+    fn state(&self) -> MutexGuard<'_, MockState> {
+        lock(&self.state)
+    }
+}
+
+impl Drop for MockKeychainServer {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+        // Unblock the accept loop
+        let _ = TcpStream::connect(self.address);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn lock(state: &Mutex<MockState>) -> MutexGuard<'_, MockState> {
+    match state.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+/// Reads a single HTTP request from the stream and writes the response.
+fn handle_connection(stream: TcpStream, state: &Mutex<MockState>) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_owned();
+    let path = parts.next().unwrap_or_default().to_owned();
+
+    let mut headers = HashMap::new();
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.insert(name.trim().to_lowercase(), value.trim().to_owned());
+        }
+    }
+
+    let content_length = headers
+        .get("content-length")
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(0);
+    let mut body = vec![0u8; content_length];
+    reader.read_exact(&mut body)?;
+
+    let (status, response) = respond(
+        &method,
+        &path,
+        headers.get("api-key").map(String::as_str),
+        &body,
+        &mut lock(state),
+    );
+
+    write_response(stream, status, &response)
+}
+
+/// Builds the HTTP status and the JSON body answering a request.
+fn respond(
+    method: &str,
+    path: &str,
+    api_key: Option<&str>,
+    body: &[u8],
+    state: &mut MockState,
+) -> (u16, serde_json::Value) {
+    if path != KEYCHAINS_PATH {
+        return (404, error_body(404, "Not found"));
+    }
+    if method != "POST" {
+        return (405, error_body(405, "Method not allowed"));
+    }
+
+    let request: KeychainsRequest = match serde_json::from_slice(body) {
+        Ok(request) => request,
+        Err(e) => return (400, error_body(400, &e.to_string())),
+    };
+    state.requests.push(request.clone());
+
+    match state.failures.pop_front() {
+        Some(Failure::Status(status)) => return (status, error_body(status, "Scripted failure")),
+        Some(Failure::ResultCode(code, msg)) => return (200, result_body(None, code, &msg)),
+        Some(Failure::MalformedBody) => return (200, json!(["Scripted malformed body"])),
+        None => {}
+    }
+
+    if let Some(expected) = &state.api_key {
+        if api_key != Some(expected.as_str()) {
+            return (403, error_body(403, "Invalid api key"));
+        }
+    }
+
+    let mut data = Vec::with_capacity(request.keychains.len());
+
+    for keychain in &request.keychains {
+        let mut entries = Vec::with_capacity(keychain.len());
+
+        for entry in keychain {
+            match state.keychains.get(&entry.aes_ciphertext) {
+                Some(fixture) => entries.push(KeychainFeaturePoint {
+                    feature_point: entry.feature_point,
+                    aes_key: fixture.aes_key.clone(),
+                    aes_iv: fixture.aes_iv.clone(),
+                }),
+                None => {
+                    return (
+                        200,
+                        result_body(None, UNKNOWN_CIPHERTEXT_CODE, "Unknown ciphertext"),
+                    )
+                }
+            }
+        }
+
+        data.push(entries);
+    }
+
+    (200, result_body(Some(data), 0, "success"))
+}
+
+fn result_body(
+    data: Option<Vec<Vec<KeychainFeaturePoint>>>,
+    code: u8,
+    msg: &str,
+) -> serde_json::Value {
+    json!({
+        "data": data,
+        "result": {
+            "code": code,
+            "msg": msg,
+        },
+    })
+}
+
+fn error_body(status: u16, msg: &str) -> serde_json::Value {
+    json!({
+        "status": status,
+        "msg": msg,
+    })
+}
+
+fn write_response(mut stream: TcpStream, status: u16, body: &serde_json::Value) -> io::Result<()> {
+    let body = body.to_string();
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        _ => "Unknown",
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
@@ -0,0 +1,208 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+use std::time::Duration;
+
+use dji_log_mock_server::{MockKeychain, MockKeychainServer, UNKNOWN_CIPHERTEXT_CODE};
+use dji_log_parser::keychain::{
+    EncodedKeychainFeaturePoint, FeaturePoint, KeychainClient, KeychainResponseCode,
+    KeychainsRequest,
+};
+use dji_log_parser::Error;
+
+const API_KEY: &str = "__DJI_API_KEY__";
+
+/// Wakes the thread blocked in `block_on`.
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut context = Context::from_waker(&waker);
+
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+fn start_server() -> MockKeychainServer {
+    MockKeychainServer::start(HashMap::from([(
+        "__AES_CIPHERTEXT__".to_owned(),
+        MockKeychain::new("__AES_KEY__", "__AES_IV__"),
+    )]))
+    .unwrap()
+}
+
+fn request(ciphertext: &str) -> KeychainsRequest {
+    KeychainsRequest {
+        version: 1,
+        department: 3,
+        keychains: vec![vec![EncodedKeychainFeaturePoint {
+            feature_point: FeaturePoint::BaseFeature,
+            aes_ciphertext: ciphertext.to_owned(),
+        }]],
+    }
+}
+
+#[test]
+fn fetch_returns_fixture_keychains() {
+    let server = start_server();
+
+    let keychains = request("__AES_CIPHERTEXT__")
+        .fetch(API_KEY, Some(&server.endpoint()))
+        .unwrap();
+
+    assert_eq!(keychains.len(), 1);
+    assert_eq!(keychains[0][0].feature_point, FeaturePoint::BaseFeature);
+    assert_eq!(keychains[0][0].aes_key, "__AES_KEY__");
+    assert_eq!(keychains[0][0].aes_iv, "__AES_IV__");
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn fetch_async_returns_fixture_keychains() {
+    let server = start_server();
+
+    let keychains =
+        block_on(request("__AES_CIPHERTEXT__").fetch_async(API_KEY, Some(&server.endpoint())))
+            .unwrap();
+
+    assert_eq!(keychains[0][0].aes_key, "__AES_KEY__");
+}
+
+#[test]
+fn invalid_api_key() {
+    let server = start_server();
+    server.require_api_key(API_KEY);
+
+    let result = request("__AES_CIPHERTEXT__").fetch("__WRONG_KEY__", Some(&server.endpoint()));
+
+    assert!(matches!(result, Err(Error::ApiKeyError)));
+}
+
+#[test]
+fn http_failures() {
+    let server = start_server();
+    server.push_failure(429);
+    server.push_failure(503);
+    server.push_failure(418);
+
+    let request = request("__AES_CIPHERTEXT__");
+    let endpoint = server.endpoint();
+
+    assert!(matches!(
+        request.fetch(API_KEY, Some(&endpoint)),
+        Err(Error::RateLimited)
+    ));
+    assert!(matches!(
+        request.fetch(API_KEY, Some(&endpoint)),
+        Err(Error::ServerUnavailable(503))
+    ));
+    assert!(matches!(
+        request.fetch(API_KEY, Some(&endpoint)),
+        Err(Error::NetworkRequestStatus(418))
+    ));
+    assert!(request.fetch(API_KEY, Some(&endpoint)).is_ok());
+}
+
+#[test]
+fn client_retries_rate_limit() {
+    let server = start_server();
+    server.push_failure(429);
+    server.push_failure(502);
+
+    let client = KeychainClient::new(API_KEY)
+        .with_endpoint(&server.endpoint())
+        .with_retries(2, Duration::from_millis(1));
+
+    assert!(client.fetch(&request("__AES_CIPHERTEXT__")).is_ok());
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[test]
+fn client_gives_up_after_retries() {
+    let server = start_server();
+    server.push_failure(429);
+    server.push_failure(429);
+
+    let client = KeychainClient::new(API_KEY)
+        .with_endpoint(&server.endpoint())
+        .with_retries(1, Duration::from_millis(1));
+
+    assert!(matches!(
+        client.fetch(&request("__AES_CIPHERTEXT__")),
+        Err(Error::RateLimited)
+    ));
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[test]
+fn client_does_not_retry_result_codes() {
+    let server = start_server();
+    server.push_result_code(3, "Internal error");
+
+    let client = KeychainClient::new(API_KEY)
+        .with_endpoint(&server.endpoint())
+        .with_retries(3, Duration::from_millis(1));
+
+    assert!(matches!(
+        client.fetch(&request("__AES_CIPHERTEXT__")),
+        Err(Error::ApiResponse(KeychainResponseCode::Unknown(3), _))
+    ));
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn unknown_ciphertext() {
+    let server = start_server();
+
+    let result = request("__UNKNOWN__").fetch(API_KEY, Some(&server.endpoint()));
+
+    assert!(matches!(
+        result,
+        Err(Error::ApiResponse(KeychainResponseCode::Unknown(UNKNOWN_CIPHERTEXT_CODE), msg))
+            if msg == "Unknown ciphertext"
+    ));
+}
+
+#[test]
+fn result_codes() {
+    let server = start_server();
+    server.push_result_code(3, "Internal error");
+    server.push_result_code(42, "Unexpected");
+
+    let request = request("__AES_CIPHERTEXT__");
+    let endpoint = server.endpoint();
+
+    // Result codes are not documented, none is mapped to a specific error
+    assert!(matches!(
+        request.fetch(API_KEY, Some(&endpoint)),
+        Err(Error::ApiResponse(KeychainResponseCode::Unknown(3), msg)) if msg == "Internal error"
+    ));
+    assert!(matches!(
+        request.fetch(API_KEY, Some(&endpoint)),
+        Err(Error::ApiResponse(KeychainResponseCode::Unknown(42), msg)) if msg == "Unexpected"
+    ));
+}
+
+#[test]
+fn malformed_body() {
+    let server = start_server();
+    server.push_malformed_body();
+
+    let result = request("__AES_CIPHERTEXT__").fetch(API_KEY, Some(&server.endpoint()));
+
+    assert!(matches!(result, Err(Error::ApiError(_))));
+}
@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+
+use dji_log_mock_server::{MockKeychain, MockKeychainServer, UNKNOWN_CIPHERTEXT_CODE};
+use dji_log_parser::keychain::{
+    EncodedKeychainFeaturePoint, FeaturePoint, KeychainClient, KeychainResponseCode,
+    KeychainsRequest,
+};
+use dji_log_parser::Error;
+
+fn request(ciphertexts: &[&str]) -> KeychainsRequest {
+    KeychainsRequest {
+        version: 1,
+        department: 3,
+        keychains: vec![ciphertexts
+            .iter()
+            .map(|ciphertext| EncodedKeychainFeaturePoint {
+                feature_point: FeaturePoint::BaseFeature,
+                aes_ciphertext: ciphertext.to_string(),
+            })
+            .collect()],
+    }
+}
+
+#[test]
+fn failed_batch_only_fails_its_requests() {
+    let server = MockKeychainServer::start(HashMap::from([
+        ("A".to_owned(), MockKeychain::new("KEY_A", "IV_A")),
+        ("C".to_owned(), MockKeychain::new("KEY_C", "IV_C")),
+    ]))
+    .unwrap();
+    let client = KeychainClient::new("__DJI_API_KEY__").with_endpoint(&server.endpoint());
+
+    let requests = [request(&["A"]), request(&["B"]), request(&["C"])];
+    let results = client.fetch_batch(&requests, 1);
+
+    assert_eq!(server.requests().len(), 3);
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].as_ref().unwrap()[0][0].aes_key, "KEY_A");
+    assert!(matches!(
+        results[1],
+        Err(Error::ApiResponse(
+            KeychainResponseCode::Unknown(UNKNOWN_CIPHERTEXT_CODE),
+            _
+        ))
+    ));
+    assert_eq!(results[2].as_ref().unwrap()[0][0].aes_key, "KEY_C");
+}
+
+#[test]
+fn batch_error_fails_every_request_of_the_batch() {
+    let server = MockKeychainServer::start(HashMap::from([(
+        "A".to_owned(),
+        MockKeychain::new("KEY_A", "IV_A"),
+    )]))
+    .unwrap();
+    let client = KeychainClient::new("__DJI_API_KEY__").with_endpoint(&server.endpoint());
+
+    let requests = [request(&["A"]), request(&["B"])];
+    let results = client.fetch_batch(&requests, 10);
+
+    assert_eq!(server.requests().len(), 1);
+    assert!(results.iter().all(|result| matches!(
+        result,
+        Err(Error::ApiResponse(
+            KeychainResponseCode::Unknown(UNKNOWN_CIPHERTEXT_CODE),
+            _
+        ))
+    )));
+}
@@ -1,6 +1,7 @@
 use dji_log_parser::keychain::KeychainFeaturePoint;
 use dji_log_parser::layout::auxiliary::Department;
 use dji_log_parser::DJILog;
+use std::io::Cursor;
 use wasm_bindgen::{prelude::wasm_bindgen, JsCast, JsValue};
 
 #[wasm_bindgen]
@@ -8,6 +9,9 @@ extern "C" {
     #[wasm_bindgen(typescript_type = "Details")]
     pub type JSDetails;
 
+    #[wasm_bindgen(typescript_type = "LogSummary")]
+    pub type JSLogSummary;
+
     #[wasm_bindgen(typescript_type = "KeychainsRequest")]
     pub type JSKeychainsRequest;
 
@@ -44,6 +48,25 @@ impl DJILogWrapper {
             .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
+    /// Reads the metadata of a DJI log without decoding any record.
+    ///
+    /// Only the Prefix and the Details are read, or the first two Auxiliary blocks for logs
+    /// version 13 and above.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - An Uint8Array representing the DJI log file.
+    ///
+    #[wasm_bindgen]
+    pub fn probe(bytes: Vec<u8>) -> Result<JSLogSummary, JsValue> {
+        let summary =
+            DJILog::probe(Cursor::new(bytes)).map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+        serde_wasm_bindgen::to_value(&summary)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+            .map(|value| value.unchecked_into())
+    }
+
     /// Get version
     #[wasm_bindgen(getter)]
     pub fn version(&self) -> u8 {
@@ -0,0 +1,11 @@
+#![no_main]
+
+use dji_log_parser::DJILog;
+use dji_log_parser_fuzz::{keychains, AES_IV, AES_KEY};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(parser) = DJILog::from_bytes(data.to_vec()) {
+        let _ = parser.frames(keychains(&AES_KEY, &AES_IV));
+    }
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+use dji_log_parser::DJILog;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = DJILog::from_bytes(data.to_vec());
+});
@@ -0,0 +1,13 @@
+#![no_main]
+
+use std::io::Cursor;
+
+use dji_log_parser::DJILog;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = DJILog::probe(Cursor::new(data));
+    if let Ok(parser) = DJILog::from_reader(Cursor::new(data.to_vec())) {
+        let _ = parser.records(None);
+    }
+});
@@ -0,0 +1,39 @@
+//! Shared helpers of the `dji-log-parser` fuzz targets.
+use base64::engine::general_purpose::STANDARD as Base64Standard;
+use base64::Engine as _;
+use dji_log_parser::keychain::{FeaturePoint, KeychainFeaturePoint};
+
+/// Feature points with encrypted records in logs version 13 and above
+const FEATURE_POINTS: [FeaturePoint; 14] = [
+    FeaturePoint::BaseFeature,
+    FeaturePoint::VisionFeature,
+    FeaturePoint::WaypointFeature,
+    FeaturePoint::AgricultureFeature,
+    FeaturePoint::AirLinkFeature,
+    FeaturePoint::AfterSalesFeature,
+    FeaturePoint::DJIFlyCustomFeature,
+    FeaturePoint::FlightHubFeature,
+    FeaturePoint::GimbalFeature,
+    FeaturePoint::RCFeature,
+    FeaturePoint::CameraFeature,
+    FeaturePoint::BatteryFeature,
+    FeaturePoint::FlySafeFeature,
+    FeaturePoint::SecurityFeature,
+];
+
+/// AES key and iv used to decrypt records of fuzzed inputs
+pub const AES_KEY: [u8; 32] = [0x42; 32];
+pub const AES_IV: [u8; 16] = [0x24; 16];
+
+/// Builds keychains with the same AES key and iv for every feature point, so encrypted records
+/// of logs version 13 and above go through the AES decoder.
+pub fn keychains(aes_key: &[u8], aes_iv: &[u8]) -> Option<Vec<Vec<KeychainFeaturePoint>>> {
+    Some(vec![FEATURE_POINTS
+        .iter()
+        .map(|feature_point| KeychainFeaturePoint {
+            feature_point: *feature_point,
+            aes_key: Base64Standard.encode(aes_key),
+            aes_iv: Base64Standard.encode(aes_iv),
+        })
+        .collect()])
+}
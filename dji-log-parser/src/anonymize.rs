@@ -0,0 +1,212 @@
+use crc64::crc64;
+
+use crate::layout::details::Details;
+use crate::record::Record;
+
+/// RC GPS coordinates are stored as integers, in 1e-7 degrees
+const RC_GPS_SCALE: f64 = 10_000_000.0;
+
+/// Secret transform moving coordinates away from their true location.
+///
+/// Coordinates are first rotated around the starting point of the log, then offset. Distances
+/// and shapes of the flight path are approximately preserved, while the location is not.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CoordinateTransform {
+    /// Latitude offset in degrees
+    pub latitude_offset: f64,
+    /// Longitude offset in degrees
+    pub longitude_offset: f64,
+    /// Rotation around the starting point in degrees, counterclockwise
+    pub rotation: f64,
+}
+
+impl CoordinateTransform {
+    /// Creates a transform from explicit offsets and rotation.
+    ///
+    /// # Arguments
+    ///
+    /// * `latitude_offset` - Latitude offset in degrees.
+    /// * `longitude_offset` - Longitude offset in degrees.
+    /// * `rotation` - Rotation around the starting point in degrees, counterclockwise.
+    pub fn new(latitude_offset: f64, longitude_offset: f64, rotation: f64) -> Self {
+        CoordinateTransform {
+            latitude_offset,
+            longitude_offset,
+            rotation,
+        }
+    }
+
+    /// Derives a transform from a secret.
+    ///
+    /// The same secret always gives the same transform, so several logs of a same flight area
+    /// can be anonymized consistently. The latitude is offset by up to 10 degrees to limit the
+    /// distortion of the flight path, the longitude by up to 180 degrees.
+    ///
+    /// # Arguments
+    ///
+    /// * `secret` - The secret bytes, not stored in the anonymized output.
+    pub fn from_secret(secret: &[u8]) -> Self {
+        // Map a hash of the secret to [-1.0, 1.0]
+        let unit = |seed: u64| (crc64(seed, secret) as f64 / u64::MAX as f64) * 2.0 - 1.0;
+
+        CoordinateTransform {
+            latitude_offset: unit(1) * 10.0,
+            longitude_offset: unit(2) * 180.0,
+            rotation: unit(3) * 180.0,
+        }
+    }
+
+    /// Applies the transform to a coordinate.
+    ///
+    /// # Arguments
+    ///
+    /// * `origin` - The `(latitude, longitude)` rotation center, in degrees.
+    /// * `latitude` - Latitude in degrees.
+    /// * `longitude` - Longitude in degrees.
+    ///
+    /// # Returns
+    ///
+    /// The transformed `(latitude, longitude)`. `(0, 0)`, used by aircrafts without GPS fix, is
+    /// left untouched.
+    pub fn apply(&self, origin: (f64, f64), latitude: f64, longitude: f64) -> (f64, f64) {
+        if latitude == 0.0 && longitude == 0.0 {
+            return (latitude, longitude);
+        }
+
+        let (origin_latitude, origin_longitude) = origin;
+        // Local equirectangular projection around the origin
+        let scale = origin_latitude.to_radians().cos().max(f64::EPSILON);
+        let x = (longitude - origin_longitude) * scale;
+        let y = latitude - origin_latitude;
+
+        let (sin, cos) = self.rotation.to_radians().sin_cos();
+        let rotated_x = x * cos - y * sin;
+        let rotated_y = x * sin + y * cos;
+
+        let latitude = (origin_latitude + rotated_y + self.latitude_offset).clamp(-90.0, 90.0);
+        let longitude = origin_longitude + rotated_x / scale + self.longitude_offset;
+        let longitude = (longitude + 180.0).rem_euclid(360.0) - 180.0;
+
+        (latitude, longitude)
+    }
+}
+
+/// Scrubs identity and location data from a log.
+///
+/// - Serial numbers of the aircraft, camera, remote controller, battery and components are blanked.
+/// - Address strings of the details are blanked.
+/// - Coordinates of the details, OSD, Home, RC GPS and App GPS records are moved with a
+///   `CoordinateTransform`.
+/// - JPEG images, records of unknown layout and invalid data are removed, as they may embed
+///   identity or location data that cannot be scrubbed.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use dji_log_parser::anonymize::{Anonymizer, CoordinateTransform};
+/// # use dji_log_parser::DJILog;
+/// # let parser = DJILog::from_bytes(std::fs::read("path/to/log.txt")?)?;
+/// let anonymizer = Anonymizer::new(CoordinateTransform::from_secret(b"__SECRET__"));
+/// let bytes = parser.anonymize(&anonymizer)?;
+/// # Ok::<(), dji_log_parser::Error>(())
+/// ```
+///
+#[derive(Debug, Clone, Copy)]
+pub struct Anonymizer {
+    transform: CoordinateTransform,
+}
+
+impl Anonymizer {
+    pub fn new(transform: CoordinateTransform) -> Self {
+        Anonymizer { transform }
+    }
+
+    /// Anonymizes the details and records of a log in place.
+    ///
+    /// The starting point of the details is used as the rotation center of all coordinates.
+    ///
+    /// # Arguments
+    ///
+    /// * `details` - The log details.
+    /// * `records` - The log records. JPEG images, unknown records and invalid data are removed.
+    pub fn anonymize(&self, details: &mut Details, records: &mut Vec<Record>) {
+        let origin = (details.latitude, details.longitude);
+
+        self.anonymize_details(details, origin);
+
+        records.retain(|record| {
+            !matches!(
+                record,
+                Record::JPEG(_) | Record::Unknown(_, _) | Record::Invalid(_)
+            )
+        });
+        for record in records.iter_mut() {
+            self.anonymize_record(record, origin);
+        }
+    }
+
+    fn anonymize_details(&self, details: &mut Details, origin: (f64, f64)) {
+        details.sub_street.clear();
+        details.street.clear();
+        details.city.clear();
+        details.area.clear();
+
+        details.aircraft_sn.clear();
+        details.camera_sn.clear();
+        details.rc_sn.clear();
+        details.battery_sn.clear();
+
+        (details.latitude, details.longitude) =
+            self.transform
+                .apply(origin, details.latitude, details.longitude);
+
+        for i in 0..4 {
+            (
+                details.moment_pic_latitude[i],
+                details.moment_pic_longitude[i],
+            ) = self.transform.apply(
+                origin,
+                details.moment_pic_latitude[i],
+                details.moment_pic_longitude[i],
+            );
+        }
+    }
+
+    fn anonymize_record(&self, record: &mut Record, origin: (f64, f64)) {
+        match record {
+            Record::OSD(osd) => {
+                (osd.latitude, osd.longitude) =
+                    self.transform.apply(origin, osd.latitude, osd.longitude);
+            }
+            Record::Home(home) => {
+                (home.latitude, home.longitude) =
+                    self.transform.apply(origin, home.latitude, home.longitude);
+            }
+            Record::AppGPS(app_gps) => {
+                (app_gps.latitude, app_gps.longitude) =
+                    self.transform
+                        .apply(origin, app_gps.latitude, app_gps.longitude);
+            }
+            Record::RCGPS(rc_gps) => {
+                let (latitude, longitude) = self.transform.apply(
+                    origin,
+                    rc_gps.latitude as f64 / RC_GPS_SCALE,
+                    rc_gps.longitude as f64 / RC_GPS_SCALE,
+                );
+                rc_gps.latitude = (latitude * RC_GPS_SCALE).round() as i32;
+                rc_gps.longitude = (longitude * RC_GPS_SCALE).round() as i32;
+            }
+            Record::Recover(recover) => {
+                recover.aircraft_sn.clear();
+                recover.camera_sn.clear();
+                recover.rc_sn.clear();
+                recover.battery_sn.clear();
+            }
+            Record::ComponentSerial(component_serial) => {
+                component_serial.serial.clear();
+            }
+            _ => {}
+        }
+    }
+}
@@ -3,15 +3,98 @@ use aes::cipher::{BlockDecryptMut, BlockSizeUser, KeyIvInit};
 use aes::Aes256;
 use crc64::crc64;
 use std::cell::RefCell;
-use std::io::{Cursor, Error, ErrorKind, Read, Result, Seek, SeekFrom};
+use std::io::{BufReader, Cursor, Error, ErrorKind, Read, Result, Seek, SeekFrom};
+use std::sync::{Mutex, MutexGuard};
 
 use crate::keychain::{FeaturePoint, Keychain};
 
 type Aes256CbcDec = cbc::Decryptor<Aes256>;
 
+/// Type of the `KeyStorageRecover` records, which are never Xor encoded
+pub(crate) const KEY_STORAGE_RECOVER_TYPE: u8 = 50;
+
 pub trait SeekRead: Seek + Read {}
 impl<T> SeekRead for T where T: Seek + Read {}
 
+/// Source of a log, shared by the log and its record iterators.
+pub(crate) type SharedReader = Mutex<Box<dyn SeekRead + Send>>;
+
+/// Locks the source of a log.
+///
+/// The reader is always positioned with an absolute seek before being used, so its state
+/// is still usable if a previous holder panicked.
+pub(crate) fn lock_reader(reader: &SharedReader) -> MutexGuard<'_, Box<dyn SeekRead + Send>> {
+    match reader.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+/// Buffered reader keeping its buffer when seeking.
+///
+/// `BufReader` discards its buffer on every seek, while records are parsed with many small
+/// seeks: the record iterator seeks to each record, and each variant of `Record` is tried
+/// in turn from the start of the record. Seeks are turned into relative seeks, which only
+/// reach the underlying reader when the target position is not buffered.
+pub struct BufferedReader<R> {
+    reader: BufReader<R>,
+    position: u64,
+}
+
+impl<R: Read + Seek> BufferedReader<R> {
+    pub fn new(mut reader: R) -> Result<Self> {
+        let position = reader.stream_position()?;
+
+        Ok(BufferedReader {
+            reader: BufReader::new(reader),
+            position,
+        })
+    }
+}
+
+impl<R: Read> Read for BufferedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let bytes_read = self.reader.read(buf)?;
+        self.position += bytes_read as u64;
+        Ok(bytes_read)
+    }
+}
+
+impl<R: Seek> Seek for BufferedReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let position = match pos {
+            SeekFrom::Start(position) => position,
+            SeekFrom::Current(offset) => {
+                self.position.checked_add_signed(offset).ok_or_else(|| {
+                    Error::new(ErrorKind::InvalidInput, "Seek to a negative position")
+                })?
+            }
+            SeekFrom::End(_) => {
+                self.position = self.reader.seek(pos)?;
+                return Ok(self.position);
+            }
+        };
+
+        // Offsets out of the i64 range are sought from the start, which discards the buffer
+        let offset = i64::try_from(position)
+            .ok()
+            .zip(i64::try_from(self.position).ok())
+            .and_then(|(position, current)| position.checked_sub(current));
+        match offset {
+            Some(offset) => self.reader.seek_relative(offset)?,
+            None => {
+                self.reader.seek(SeekFrom::Start(position))?;
+            }
+        }
+        self.position = position;
+        Ok(position)
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        Ok(self.position)
+    }
+}
+
 /// Constructs a reader based on the given record type, version, and keychain.
 ///
 /// # Arguments
@@ -24,8 +107,9 @@ impl<T> SeekRead for T where T: Seek + Read {}
 ///
 /// # Returns
 ///
-/// This function returns a `Result` containing either a boxed reader implementing `Read`
-/// or an error if one occurs during the reader's construction.
+/// This function returns a boxed reader implementing `Read`. If the reader cannot be
+/// constructed, for instance on truncated data or invalid keychains, the returned reader fails
+/// on every read so the error surfaces when the record is parsed.
 pub fn record_decoder<'a, R>(
     reader: R,
     record_type: u8,
@@ -36,44 +120,174 @@ pub fn record_decoder<'a, R>(
 where
     R: Read + Seek + 'a,
 {
-    match version {
+    match try_record_decoder(reader, record_type, version, keychain, size) {
+        Ok(decoder) => decoder,
+        Err(e) => Box::new(FailedDecoder::new(e)),
+    }
+}
+
+fn try_record_decoder<'a, R>(
+    mut reader: R,
+    record_type: u8,
+    version: u8,
+    keychain: &RefCell<Keychain>,
+    size: u16,
+) -> Result<Box<dyn SeekRead + 'a>>
+where
+    R: Read + Seek + 'a,
+{
+    Ok(match version {
         // Raw
         0..=6 => Box::new(reader),
         // Xor
-        7..=12 => Box::new(XorDecoder::new(reader, record_type)),
+        7..=12 => Box::new(XorDecoder::new(reader, record_type)?),
         // Xor + AES
         _ => {
             let feature_point = FeaturePoint::from_record_type(record_type, version);
             match feature_point {
-                FeaturePoint::PlaintextFeature => Box::new(XorDecoder::new(reader, record_type)),
+                FeaturePoint::PlaintextFeature => Box::new(XorDecoder::new(reader, record_type)?),
                 _ => {
-                    let pair = keychain
-                        .borrow()
-                        .get(&feature_point)
-                        .map(|value| (value.0.clone(), value.1.clone()));
+                    let offset = reader.stream_position()?;
+                    let entry = keychain.borrow_mut().record_entry(offset, feature_point);
 
-                    match pair {
-                        Some(value) => {
+                    match entry {
+                        Some(((iv, key), is_retry)) => {
+                            // firstChar and lastChar are not part of the content
+                            let size = size.checked_sub(2).ok_or_else(|| {
+                                Error::new(ErrorKind::InvalidData, "Record too short")
+                            })?;
                             let aes_reader = AesDecoder::new(
-                                XorDecoder::new(reader, record_type),
-                                &value.0,
-                                &value.1,
-                                size - 2, // firstChar and lastChar are not part of the content
-                            );
+                                XorDecoder::new(reader, record_type)?,
+                                &iv,
+                                &key,
+                                size,
+                            )?;
 
                             // Update keychain with next iv
-                            keychain.borrow_mut().insert(
-                                feature_point,
-                                (aes_reader.next_iv.clone(), value.1.clone()),
-                            );
+                            if !is_retry {
+                                keychain.borrow_mut().update_record_entry(
+                                    feature_point,
+                                    aes_reader.next_iv.clone(),
+                                    aes_reader.is_decrypted,
+                                );
+                            }
+
+                            if !aes_reader.is_decrypted {
+                                return Err(Error::new(
+                                    ErrorKind::InvalidData,
+                                    format!(
+                                        "AES padding check failed for {:?}, the keychain may be wrong",
+                                        feature_point
+                                    ),
+                                ));
+                            }
 
                             Box::new(aes_reader)
                         }
-                        None => Box::new(XorDecoder::new(reader, record_type)),
+                        None => Box::new(XorDecoder::new(reader, record_type)?),
                     }
                 }
             }
         }
+    })
+}
+
+/// Reads the decoded content of a record, without the record type, length and end byte.
+///
+/// # Arguments
+///
+/// * `reader` - A reader positioned at the start of the record data, after the record length.
+/// * `record_type` - The type of record to be read.
+/// * `version` - The prefix version.
+/// * `keychain` - A reference to a keychain for decryption. A record already decoded at the
+///   same offset is decrypted again with the same entry, and the keychain is not updated.
+/// * `size` - The declared length of the record.
+///
+/// # Returns
+///
+/// The content bytes, Xor decoded and AES decrypted. `KeyStorageRecover` records are
+/// returned as they were read.
+pub(crate) fn read_record_content<R>(
+    mut reader: R,
+    record_type: u8,
+    version: u8,
+    keychain: &RefCell<Keychain>,
+    size: u16,
+) -> Result<Vec<u8>>
+where
+    R: Read + Seek,
+{
+    if record_type == KEY_STORAGE_RECOVER_TYPE {
+        let mut content = vec![0u8; size as usize];
+        reader.read_exact(&mut content)?;
+        return Ok(content);
+    }
+
+    let feature_point = FeaturePoint::from_record_type(record_type, version);
+    let is_encrypted = version >= 13
+        && feature_point != FeaturePoint::PlaintextFeature
+        && keychain.borrow().contains(feature_point);
+
+    let mut decoder = try_record_decoder(reader, record_type, version, keychain, size)?;
+
+    let length = if version <= 6 {
+        size as u64
+    } else if is_encrypted {
+        // Decrypted content is buffered, without its padding
+        let length = decoder.seek(SeekFrom::End(0))?;
+        decoder.seek(SeekFrom::Start(0))?;
+        length
+    } else {
+        // firstChar and lastChar are not part of the content
+        size.checked_sub(2)
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "Record too short"))? as u64
+    };
+
+    let mut content = vec![0u8; length as usize];
+    decoder.read_exact(&mut content)?;
+    Ok(content)
+}
+
+/// Constructs a Xor reader, failing on every read if the Xor key cannot be read.
+pub fn xor_decoder<'a, R>(reader: R, record_type: u8) -> Box<dyn SeekRead + 'a>
+where
+    R: Read + Seek + 'a,
+{
+    match XorDecoder::new(reader, record_type) {
+        Ok(decoder) => Box::new(decoder),
+        Err(e) => Box::new(FailedDecoder::new(e)),
+    }
+}
+
+/// Reader returned when a record decoder cannot be constructed.
+/// Every read and seek fails with the construction error.
+struct FailedDecoder {
+    kind: ErrorKind,
+    message: String,
+}
+
+impl FailedDecoder {
+    fn new(error: Error) -> Self {
+        FailedDecoder {
+            kind: error.kind(),
+            message: error.to_string(),
+        }
+    }
+
+    fn error(&self) -> Error {
+        Error::new(self.kind, self.message.clone())
+    }
+}
+
+impl Read for FailedDecoder {
+    fn read(&mut self, _buf: &mut [u8]) -> Result<usize> {
+        Err(self.error())
+    }
+}
+
+impl Seek for FailedDecoder {
+    fn seek(&mut self, _pos: SeekFrom) -> Result<u64> {
+        Err(self.error())
     }
 }
 
@@ -87,29 +301,57 @@ pub struct XorDecoder<R> {
 }
 
 impl<R: Read + Seek> XorDecoder<R> {
-    pub fn new(mut reader: R, record_type: u8) -> Self {
+    pub fn new(mut reader: R, record_type: u8) -> Result<Self> {
         let mut first_byte = [0u8];
-        reader.read_exact(&mut first_byte).unwrap();
+        reader.read_exact(&mut first_byte)?;
         let first_byte = first_byte[0];
 
-        let start_position = reader.stream_position().unwrap();
+        let start_position = reader.stream_position()?;
 
-        let magic: u64 = 0x123456789ABCDEF0;
-        let key = crc64(
-            first_byte.overflowing_add(record_type).0 as u64,
-            &magic.overflowing_mul(first_byte as u64).0.to_le_bytes(),
-        )
-        .to_le_bytes();
-
-        XorDecoder {
+        Ok(XorDecoder {
             reader,
-            key,
+            key: xor_key(first_byte, record_type),
             start_position,
             decode_position: 0,
-        }
+        })
     }
 }
 
+/// Derives the 8 bytes Xor key of a record from its first byte and its type.
+pub(crate) fn xor_key(first_byte: u8, record_type: u8) -> [u8; 8] {
+    let magic: u64 = 0x123456789ABCDEF0;
+    crc64(
+        first_byte.overflowing_add(record_type).0 as u64,
+        &magic.overflowing_mul(first_byte as u64).0.to_le_bytes(),
+    )
+    .to_le_bytes()
+}
+
+/// Xor encodes a record content, the inverse of `XorDecoder`.
+///
+/// # Arguments
+///
+/// * `content` - The record content to encode.
+/// * `first_byte` - The seed of the Xor key, written as the first byte of the record data.
+/// * `record_type` - The type of record to be encoded.
+///
+/// # Returns
+///
+/// The record data: the first byte followed by the encoded content.
+pub(crate) fn xor_encode(content: &[u8], first_byte: u8, record_type: u8) -> Vec<u8> {
+    let key = xor_key(first_byte, record_type);
+
+    let mut data = Vec::with_capacity(content.len() + 1);
+    data.push(first_byte);
+    data.extend(
+        content
+            .iter()
+            .enumerate()
+            .map(|(i, byte)| byte ^ key[i % 8]),
+    );
+    data
+}
+
 impl<R: Read> Read for XorDecoder<R> {
     fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
         let bytes_read = self.reader.read(buf)?;
@@ -125,7 +367,10 @@ impl<R: Seek> Seek for XorDecoder<R> {
     fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
         match pos {
             SeekFrom::Start(position) => {
-                self.decode_position = (position - self.start_position) as usize;
+                self.decode_position =
+                    position.checked_sub(self.start_position).ok_or_else(|| {
+                        Error::new(ErrorKind::InvalidInput, "Seek before record start")
+                    })? as usize;
                 self.reader.seek(pos)
             }
             SeekFrom::Current(_) => self.reader.seek(pos),
@@ -137,26 +382,35 @@ impl<R: Seek> Seek for XorDecoder<R> {
 pub struct AesDecoder {
     buffer: Cursor<Vec<u8>>,
     pub next_iv: Vec<u8>,
+    /// `false` if the Pkcs7 padding check failed, usually because of a wrong key or IV
+    pub is_decrypted: bool,
 }
 
 impl AesDecoder {
-    pub fn new<R: Read>(mut reader: R, iv: &[u8], key: &[u8], size: u16) -> AesDecoder {
+    pub fn new<R: Read>(mut reader: R, iv: &[u8], key: &[u8], size: u16) -> Result<AesDecoder> {
         let mut buffer = vec![0u8; size.into()];
-        reader.read_exact(&mut buffer).unwrap();
+        reader.read_exact(&mut buffer)?;
 
         // Get next from last block
-        let next_iv = buffer[buffer.len() - Aes256::block_size()..].to_vec();
+        let next_iv = buffer
+            .len()
+            .checked_sub(Aes256::block_size())
+            .map(|start| buffer[start..].to_vec())
+            .ok_or_else(|| {
+                Error::new(ErrorKind::InvalidData, "Record shorter than an AES block")
+            })?;
 
-        let dec: cbc::Decryptor<Aes256> = Aes256CbcDec::new_from_slices(key, iv).unwrap();
+        let dec: cbc::Decryptor<Aes256> = Aes256CbcDec::new_from_slices(key, iv)
+            .map_err(|_| Error::new(ErrorKind::InvalidInput, "Invalid AES key or iv length"))?;
         let plaintext = dec
             .decrypt_padded_mut::<Pkcs7>(&mut buffer)
-            .unwrap_or_default()
-            .to_vec();
+            .map(|plaintext| plaintext.to_vec());
 
-        AesDecoder {
-            buffer: Cursor::new(plaintext.to_vec()),
+        Ok(AesDecoder {
+            is_decrypted: plaintext.is_ok(),
+            buffer: Cursor::new(plaintext.unwrap_or_default()),
             next_iv,
-        }
+        })
     }
 }
 
@@ -1,5 +1,7 @@
 use thiserror::Error;
 
+use crate::keychain::KeychainResponseCode;
+
 pub type Result<T> = std::result::Result<T, Error>;
 
 #[derive(Debug, Error)]
@@ -11,6 +13,15 @@ pub enum Error {
     #[error("DJI Api error: {0}")]
     ApiError(String),
 
+    #[error("DJI Api error {0:?}: {1}")]
+    ApiResponse(KeychainResponseCode, String),
+
+    #[error("DJI Api rate limit exceeded")]
+    RateLimited,
+
+    #[error("DJI Api unavailable, status: {0}")]
+    ServerUnavailable(u16),
+
     #[error("Keychain is required")]
     KeychainRequired,
 
@@ -34,4 +45,13 @@ pub enum Error {
 
     #[error("Network connection error")]
     NetworkConnection,
+
+    #[error("Unsupported log version: {0}")]
+    UnsupportedVersion(u8),
+
+    #[error("{0} records failed to decrypt, the keychains may be wrong")]
+    DecryptionFailed(usize),
+
+    #[error("Record of type {0} is too large to be written: {1} bytes")]
+    RecordTooLarge(u8, usize),
 }
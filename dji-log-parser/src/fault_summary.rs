@@ -0,0 +1,95 @@
+use serde::Serialize;
+#[cfg(target_arch = "wasm32")]
+use tsify_next::Tsify;
+
+use crate::record::osd::{ImuInitFailReason, MotorStartFailedCause};
+use crate::record::Record;
+
+/// Summary of the faults reported by the OSD records of a flight, see `DJILog::fault_summary`.
+///
+/// Motor, IMU, compass and barometer fault flags of OSD records are counted. After-sales
+/// records are not decoded, and are not part of the summary.
+///
+#[derive(Serialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(target_arch = "wasm32", derive(Tsify))]
+pub struct FaultSummary {
+    /// Number of OSD records reporting a blocked motor
+    pub motor_blocked_count: u32,
+    /// Causes of failed motor starts, without duplicates
+    pub motor_start_failed_causes: Vec<MotorStartFailedCause>,
+    /// Number of OSD records reporting a propeller catapult
+    pub propeller_catapult_count: u32,
+    /// Number of OSD records reporting not enough motor force
+    pub not_enough_force_count: u32,
+    /// Number of OSD records reporting vibrations
+    pub vibration_count: u32,
+    /// Reasons of IMU initialization failures, without duplicates.
+    /// `MonitorError` is left out, as it is the value reported when no failure is set
+    pub imu_init_fail_reasons: Vec<ImuInitFailReason>,
+    /// Number of OSD records reporting an accelerometer over range
+    pub accelerometer_over_range_count: u32,
+    /// Number of OSD records reporting a dead barometer in air
+    pub barometer_dead_in_air_count: u32,
+    /// Number of OSD records reporting a compass error
+    pub compass_error_count: u32,
+}
+
+impl FaultSummary {
+    /// Builds the fault summary of a flight from its records.
+    ///
+    /// # Arguments
+    /// - `records`: A vector or an iterator of `Record` objects representing the raw log data.
+    ///
+    pub fn from_records(records: impl IntoIterator<Item = Record>) -> Self {
+        let mut summary = FaultSummary::default();
+
+        for record in records {
+            if let Record::OSD(osd) = record {
+                summary.motor_blocked_count += osd.is_motor_blocked as u32;
+                summary.propeller_catapult_count += osd.is_propeller_catapult as u32;
+                summary.not_enough_force_count += osd.is_not_enough_force as u32;
+                summary.vibration_count += osd.is_vibrating as u32;
+                summary.accelerometer_over_range_count += osd.is_acceletor_over_range as u32;
+                summary.barometer_dead_in_air_count += osd.is_barometer_dead_in_air as u32;
+                summary.compass_error_count += osd.is_compass_error as u32;
+
+                let cause = u8::from(osd.motor_start_failed_cause);
+                if cause != u8::from(MotorStartFailedCause::None)
+                    && !summary
+                        .motor_start_failed_causes
+                        .iter()
+                        .any(|known| u8::from(*known) == cause)
+                {
+                    summary
+                        .motor_start_failed_causes
+                        .push(osd.motor_start_failed_cause);
+                }
+
+                let reason = u8::from(osd.imu_init_fail_reason);
+                if reason != u8::from(ImuInitFailReason::MonitorError)
+                    && !summary
+                        .imu_init_fail_reasons
+                        .iter()
+                        .any(|known| u8::from(*known) == reason)
+                {
+                    summary.imu_init_fail_reasons.push(osd.imu_init_fail_reason);
+                }
+            }
+        }
+
+        summary
+    }
+
+    /// Returns `true` if any motor, IMU, compass or barometer fault was reported.
+    pub fn has_fault(&self) -> bool {
+        self.motor_blocked_count > 0
+            || !self.motor_start_failed_causes.is_empty()
+            || self.propeller_catapult_count > 0
+            || self.not_enough_force_count > 0
+            || !self.imu_init_fail_reasons.is_empty()
+            || self.accelerometer_over_range_count > 0
+            || self.barometer_dead_in_air_count > 0
+            || self.compass_error_count > 0
+    }
+}
@@ -2,6 +2,8 @@ use serde::Serialize;
 #[cfg(target_arch = "wasm32")]
 use tsify_next::Tsify;
 
+use crate::utils::append_message;
+
 #[derive(Serialize, Debug, Default, Clone)]
 #[serde(rename_all = "camelCase")]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
@@ -11,3 +13,15 @@ pub struct FrameApp {
     // App warning
     pub warn: String,
 }
+
+impl FrameApp {
+    /// Appends a message to the app tip.
+    pub(crate) fn append_tip(&mut self, message: impl Into<String>) {
+        self.tip = append_message(std::mem::take(&mut self.tip), message);
+    }
+
+    /// Appends a message to the app warning.
+    pub(crate) fn append_warn(&mut self, message: impl Into<String>) {
+        self.warn = append_message(std::mem::take(&mut self.warn), message);
+    }
+}
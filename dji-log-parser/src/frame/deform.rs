@@ -0,0 +1,19 @@
+use serde::Serialize;
+#[cfg(target_arch = "wasm32")]
+use tsify_next::Tsify;
+
+use crate::record::deform::{DeformMode, DeformStatus};
+
+#[derive(Serialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(target_arch = "wasm32", derive(Tsify))]
+pub struct FrameDeform {
+    /// Indicates if the landing gear is protected from deformation
+    pub is_deform_protected: bool,
+    /// Current state of the landing gear
+    #[cfg_attr(target_arch = "wasm32", tsify(optional))]
+    pub deform_status: Option<DeformStatus>,
+    /// Current mode of the landing gear
+    #[cfg_attr(target_arch = "wasm32", tsify(optional))]
+    pub deform_mode: Option<DeformMode>,
+}
@@ -51,7 +51,7 @@ impl From<Details> for FrameDetails {
             aircraft_sn: value.aircraft_sn.clone(),
             camera_sn: value.camera_sn.clone(),
             rc_sn: value.rc_sn.clone(),
-            app_platform: value.app_platform.clone(),
+            app_platform: value.app_platform,
             app_version: value.app_version.clone(),
         }
     }
@@ -0,0 +1,20 @@
+use serde::Serialize;
+#[cfg(target_arch = "wasm32")]
+use tsify_next::Tsify;
+
+use crate::record::mc_param::FailSafeProtectionType;
+
+#[derive(Serialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(target_arch = "wasm32", derive(Tsify))]
+pub struct FrameFlightController {
+    /// Action executed when the remote controller signal is lost
+    #[cfg_attr(target_arch = "wasm32", tsify(optional))]
+    pub fail_safe_protection: Option<FailSafeProtectionType>,
+    /// Indicates if the monocular visual odometry is enabled
+    pub is_mvo_enabled: bool,
+    /// Indicates if obstacle avoidance is enabled
+    pub is_avoid_obstacle_enabled: bool,
+    /// Indicates if obstacle avoidance is enabled by the user
+    pub is_user_avoid_enabled: bool,
+}
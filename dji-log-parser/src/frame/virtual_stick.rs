@@ -0,0 +1,34 @@
+use serde::Serialize;
+#[cfg(target_arch = "wasm32")]
+use tsify_next::Tsify;
+
+use crate::record::virtual_stick::{
+    VirtualStickFlightCoordinateSystem, VirtualStickRollPitchControlMode,
+    VirtualStickVerticalControlMode, VirtualStickYawControlMode,
+};
+
+#[derive(Serialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(target_arch = "wasm32", derive(Tsify))]
+pub struct FrameVirtualStick {
+    /// Meaning of the throttle value, velocity or altitude
+    #[cfg_attr(target_arch = "wasm32", tsify(optional))]
+    pub vertical_control_mode: Option<VirtualStickVerticalControlMode>,
+    /// Meaning of the roll and pitch values, angle or velocity
+    #[cfg_attr(target_arch = "wasm32", tsify(optional))]
+    pub roll_pitch_control_mode: Option<VirtualStickRollPitchControlMode>,
+    /// Meaning of the yaw value, angle or angular velocity
+    #[cfg_attr(target_arch = "wasm32", tsify(optional))]
+    pub yaw_control_mode: Option<VirtualStickYawControlMode>,
+    /// Coordinate system of the roll and pitch values
+    #[cfg_attr(target_arch = "wasm32", tsify(optional))]
+    pub coordinate_system: Option<VirtualStickFlightCoordinateSystem>,
+    /// Roll input sent by the SDK application
+    pub roll: f32,
+    /// Pitch input sent by the SDK application
+    pub pitch: f32,
+    /// Yaw input sent by the SDK application
+    pub yaw: f32,
+    /// Throttle input sent by the SDK application
+    pub throttle: f32,
+}
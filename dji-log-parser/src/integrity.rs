@@ -0,0 +1,169 @@
+use binrw::Endian;
+use serde::Serialize;
+use std::io::SeekFrom;
+#[cfg(target_arch = "wasm32")]
+use tsify_next::Tsify;
+
+use crate::decoder::SeekRead;
+use crate::record::END_BYTE;
+use crate::utils::read_jpeg;
+
+/// Result of an integrity check.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(target_arch = "wasm32", derive(Tsify))]
+pub enum IntegrityStatus {
+    /// The check succeeded
+    Pass,
+    /// The check failed, the log was altered or corrupted
+    Fail,
+    /// The check could not be performed, like decrypting records without keychains, or
+    /// computing a checksum of unknown algorithm
+    Unknown,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(target_arch = "wasm32", derive(Tsify))]
+pub enum IntegrityCheckKind {
+    /// The Details block, or the Auxiliary blocks, are complete, and the offsets of the prefix
+    /// match the blocks and the records section
+    Layout,
+    /// Records are framed end to end up to the end of the records section: each declared
+    /// length ends on an end byte
+    Framing,
+    /// All records of the records section are decoded, and decrypted for logs version 13 and above
+    Records,
+    /// The `detail_info_checksum` matches the Details block
+    DetailsChecksum,
+    /// The signature of the Auxiliary Info block matches the log
+    Signature,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(target_arch = "wasm32", derive(Tsify))]
+pub struct IntegrityCheck {
+    pub kind: IntegrityCheckKind,
+    pub status: IntegrityStatus,
+    /// Reason of the status
+    pub message: String,
+}
+
+impl IntegrityCheck {
+    pub(crate) fn new(
+        kind: IntegrityCheckKind,
+        status: IntegrityStatus,
+        message: impl Into<String>,
+    ) -> Self {
+        IntegrityCheck {
+            kind,
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+/// Integrity checks of a DJI log, see `DJILog::verify_integrity`.
+///
+/// The overall status is `Fail` if any check failed, `Pass` if all checks passed, and `Unknown`
+/// otherwise.
+///
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(target_arch = "wasm32", derive(Tsify))]
+pub struct IntegrityReport {
+    pub status: IntegrityStatus,
+    pub checks: Vec<IntegrityCheck>,
+}
+
+impl IntegrityReport {
+    pub(crate) fn new(checks: Vec<IntegrityCheck>) -> Self {
+        let status = if checks
+            .iter()
+            .any(|check| check.status == IntegrityStatus::Fail)
+        {
+            IntegrityStatus::Fail
+        } else if checks
+            .iter()
+            .all(|check| check.status == IntegrityStatus::Pass)
+        {
+            IntegrityStatus::Pass
+        } else {
+            IntegrityStatus::Unknown
+        };
+
+        IntegrityReport { status, checks }
+    }
+
+    /// Returns the result of a check.
+    pub fn check(&self, kind: IntegrityCheckKind) -> Option<&IntegrityCheck> {
+        self.checks.iter().find(|check| check.kind == kind)
+    }
+}
+
+/// Walks the records section from record header to record header, without decoding records.
+///
+/// JPEG images found between records are skipped up to their end marker.
+///
+/// # Returns
+///
+/// The number of records, or the reason the framing is broken.
+pub(crate) fn check_framing(
+    mut reader: &mut dyn SeekRead,
+    start_offset: u64,
+    end_offset: u64,
+    version: u8,
+) -> std::result::Result<usize, String> {
+    // Length is stored on one byte up to version 12
+    let header_size = if version <= 12 { 2 } else { 3 };
+    let mut position = start_offset;
+    let mut count = 0;
+
+    while position < end_offset {
+        let mut header = [0u8; 3];
+        reader
+            .seek(SeekFrom::Start(position))
+            .and_then(|_| reader.read_exact(&mut header[..header_size]))
+            .map_err(|_| format!("Record header is truncated at offset {}", position))?;
+
+        if header[..2] == [0xFF, 0xD8] {
+            reader
+                .seek(SeekFrom::Start(position))
+                .map_err(|e| e.to_string())?;
+            read_jpeg(&mut reader, Endian::Little, ())
+                .map_err(|_| format!("JPEG image at offset {} is corrupted", position))?;
+            position = reader.stream_position().map_err(|e| e.to_string())?;
+            continue;
+        }
+
+        let length = if version <= 12 {
+            u64::from(header[1])
+        } else {
+            u64::from(u16::from_le_bytes([header[1], header[2]]))
+        };
+
+        let end_position = position + header_size as u64 + length;
+        if end_position >= end_offset {
+            return Err(format!(
+                "Record of type {} at offset {} overruns the records section",
+                header[0], position
+            ));
+        }
+
+        let mut end_byte = [0u8];
+        reader
+            .seek(SeekFrom::Start(end_position))
+            .and_then(|_| reader.read_exact(&mut end_byte))
+            .map_err(|e| e.to_string())?;
+        if end_byte[0] != END_BYTE {
+            return Err(format!(
+                "Record of type {} at offset {} does not end with an end byte",
+                header[0], position
+            ));
+        }
+
+        position = end_position + 1;
+        count += 1;
+    }
+
+    Ok(count)
+}
@@ -1,15 +1,16 @@
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 #[cfg(target_arch = "wasm32")]
 use tsify_next::Tsify;
 
-use crate::Result;
+use crate::{Error, Result};
 
 use super::{EncodedKeychainFeaturePoint, KeychainFeaturePoint};
 
-const DEFAULT_ENDPOINT: &str = "https://dev.dji.com/openapi/v1/flight-records/keychains";
+pub(crate) const DEFAULT_ENDPOINT: &str = "https://dev.dji.com/openapi/v1/flight-records/keychains";
 
 /// Request structure for keychain API.
-#[derive(Debug, Default, Serialize, Clone)]
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub struct KeychainsRequest {
     pub version: u16,
@@ -28,11 +29,92 @@ pub struct KeychainsResponse {
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct KeychainResponseResult {
-    pub code: u8,
+    pub code: KeychainResponseCode,
     pub msg: String,
 }
 
+/// Result code of a keychain API response.
+///
+/// Only `0` is known to be a success. Other codes are not documented by the API and are kept as
+/// `Unknown` along with the message of the response.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(from = "u8")]
+pub enum KeychainResponseCode {
+    Success,
+    Unknown(u8),
+}
+
+impl From<u8> for KeychainResponseCode {
+    fn from(num: u8) -> Self {
+        match num {
+            0 => KeychainResponseCode::Success,
+            _ => KeychainResponseCode::Unknown(num),
+        }
+    }
+}
+
+impl From<KeychainResponseCode> for u8 {
+    fn from(code: KeychainResponseCode) -> Self {
+        match code {
+            KeychainResponseCode::Success => 0,
+            KeychainResponseCode::Unknown(num) => num,
+        }
+    }
+}
+
+impl KeychainsResponse {
+    /// Extracts the keychains from the response, or the error reported by the API.
+    pub(crate) fn into_keychains(self) -> Result<Vec<Vec<KeychainFeaturePoint>>> {
+        match self.result.code {
+            KeychainResponseCode::Success => match self.data {
+                Some(data) => Ok(data),
+                None => Err(Error::ApiError("Missing keychain data".to_owned())),
+            },
+            code => Err(Error::ApiResponse(code, self.result.msg)),
+        }
+    }
+}
+
+/// Maps an unsuccessful HTTP status of the keychain API to an `Error`.
+pub(crate) fn status_error(status: u16) -> Error {
+    match status {
+        403 => Error::ApiKeyError,
+        429 => Error::RateLimited,
+        500..=599 => Error::ServerUnavailable(status),
+        _ => Error::NetworkRequestStatus(status),
+    }
+}
+
 impl KeychainsRequest {
+    /// Computes a fingerprint of the encrypted keychains of the request.
+    ///
+    /// The fingerprint is a SHA-256 hash of the feature points and AES ciphertexts of each
+    /// keychain, and identifies the keychains of a log independently of the request version or
+    /// department.
+    ///
+    /// # Returns
+    ///
+    /// A 64 characters hexadecimal string.
+    pub fn fingerprint(&self) -> String {
+        let mut hasher = Sha256::new();
+        for keychain in &self.keychains {
+            // Mark keychain and ciphertext boundaries so that splitting the same feature points
+            // differently produces a different fingerprint
+            hasher.update((keychain.len() as u64).to_le_bytes());
+            for entry in keychain {
+                hasher.update((entry.feature_point as u16).to_le_bytes());
+                hasher.update((entry.aes_ciphertext.len() as u64).to_le_bytes());
+                hasher.update(entry.aes_ciphertext.as_bytes());
+            }
+        }
+
+        hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
     /// Sends a synchronous request to the keychain API.
     ///
     /// This method is only available for non-WASM targets.
@@ -91,7 +173,7 @@ pub(crate) mod native {
     use crate::keychain::KeychainFeaturePoint;
     use crate::{Error, Result};
 
-    use super::{KeychainsRequest, KeychainsResponse};
+    use super::{status_error, KeychainsRequest, KeychainsResponse};
 
     pub fn fetch(
         api_key: &str,
@@ -106,21 +188,15 @@ pub(crate) mod native {
             .timeout(Duration::from_secs(30))
             .send_string(&body)
             .map_err(|e| match e {
-                ureq::Error::Status(403, _) => Error::ApiKeyError,
-                ureq::Error::Status(status, _) => Error::NetworkRequestStatus(status),
+                ureq::Error::Status(status, _) => status_error(status),
                 _ => Error::NetworkConnection,
             })?;
 
-        let keychains_response: KeychainsResponse = response.into_json()?;
+        let keychains_response: KeychainsResponse = response
+            .into_json()
+            .map_err(|e| Error::ApiError(format!("Unable to parse response: {}", e)))?;
 
-        if keychains_response.result.code != 0 {
-            Err(Error::ApiError(keychains_response.result.msg))
-        } else {
-            match keychains_response.data {
-                Some(data) => Ok(data),
-                None => Err(Error::ApiError("Missing keychain data".to_owned())),
-            }
-        }
+        keychains_response.into_keychains()
     }
 
     #[cfg(feature = "native-async")]
@@ -137,7 +213,7 @@ pub(crate) mod native {
 
         std::thread::spawn(move || {
             let response = fetch(&api_key, &endpoint, &request);
-            tx.send(response);
+            let _ = tx.send_blocking(response);
         });
 
         rx.recv().await.map_err(|_| Error::NetworkConnection)?
@@ -154,7 +230,7 @@ pub(crate) mod wasm {
     use crate::keychain::KeychainFeaturePoint;
     use crate::{Error, Result};
 
-    use super::{KeychainsRequest, KeychainsResponse};
+    use super::{status_error, KeychainsRequest, KeychainsResponse};
 
     #[wasm_bindgen]
     extern "C" {
@@ -191,7 +267,7 @@ pub(crate) mod wasm {
             .unchecked_into();
 
         if !response.ok() {
-            return Err(Error::ApiKeyError);
+            return Err(status_error(response.status()));
         }
 
         let json = JsFuture::from(
@@ -205,13 +281,6 @@ pub(crate) mod wasm {
         let keychains_response: KeychainsResponse = serde_wasm_bindgen::from_value(json)
             .map_err(|_| Error::ApiError("Unable to parse response".to_owned()))?;
 
-        if keychains_response.result.code != 0 {
-            Err(Error::ApiError(keychains_response.result.msg))
-        } else {
-            match keychains_response.data {
-                Some(data) => Ok(data),
-                None => Err(Error::ApiError("Missing keychain data".to_owned())),
-            }
-        }
+        keychains_response.into_keychains()
     }
 }
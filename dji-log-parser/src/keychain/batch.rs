@@ -0,0 +1,247 @@
+use crate::{Error, Result};
+
+#[cfg(not(target_arch = "wasm32"))]
+use super::api::DEFAULT_ENDPOINT;
+#[cfg(not(target_arch = "wasm32"))]
+use super::KeychainClient;
+use super::{KeychainFeaturePoint, KeychainsRequest};
+
+/// A `KeychainsRequest` merging the keychains of several requests sharing a version and a department.
+///
+/// Each keychain of the merged request remembers the index of the request it comes from,
+/// so that the API response can be split back per request.
+#[derive(Debug, Clone)]
+pub struct KeychainsBatch {
+    pub request: KeychainsRequest,
+    owners: Vec<usize>,
+}
+
+impl KeychainsBatch {
+    /// Merges requests into batches of at most `max_batch_size` keychains.
+    ///
+    /// Requests are grouped by version and department, in order of first appearance. The keychains
+    /// of a single request are split across several batches when they exceed `max_batch_size`.
+    ///
+    /// # Arguments
+    ///
+    /// * `requests` - The requests to merge, usually built from several logs.
+    /// * `max_batch_size` - The maximum number of keychains in a batch.
+    ///
+    /// # Returns
+    ///
+    /// The batches to send to the keychain API. Requests without keychains produce no batch.
+    pub fn from_requests(requests: &[KeychainsRequest], max_batch_size: usize) -> Vec<Self> {
+        let max_batch_size = max_batch_size.max(1);
+        let mut groups: Vec<Vec<usize>> = Vec::new();
+
+        for (index, request) in requests.iter().enumerate() {
+            let group = groups.iter_mut().find(|group| {
+                let first = &requests[group[0]];
+                first.version == request.version && first.department == request.department
+            });
+            match group {
+                Some(group) => group.push(index),
+                None => groups.push(vec![index]),
+            }
+        }
+
+        let mut batches = Vec::new();
+
+        for group in groups {
+            let first = &requests[group[0]];
+            let mut batch = KeychainsBatch::empty(first);
+
+            for index in group {
+                for keychain in &requests[index].keychains {
+                    if batch.owners.len() == max_batch_size {
+                        batches.push(batch);
+                        batch = KeychainsBatch::empty(first);
+                    }
+                    batch.request.keychains.push(keychain.clone());
+                    batch.owners.push(index);
+                }
+            }
+
+            if !batch.owners.is_empty() {
+                batches.push(batch);
+            }
+        }
+
+        batches
+    }
+
+    fn empty(request: &KeychainsRequest) -> Self {
+        KeychainsBatch {
+            request: KeychainsRequest {
+                version: request.version,
+                department: request.department,
+                keychains: Vec::new(),
+            },
+            owners: Vec::new(),
+        }
+    }
+
+    /// Splits the keychains returned by the API for this batch back per request.
+    ///
+    /// # Arguments
+    ///
+    /// * `keychains` - The keychains returned by the API, in the order of the batch request.
+    /// * `results` - The keychains per request, indexed like the `requests` given to `from_requests`.
+    ///   Keychains are appended to the entry of the request they belong to.
+    ///
+    /// # Returns
+    ///
+    /// An error if the API did not return one keychain for each keychain of the batch.
+    pub fn split(
+        &self,
+        keychains: Vec<Vec<KeychainFeaturePoint>>,
+        results: &mut [Vec<Vec<KeychainFeaturePoint>>],
+    ) -> Result<()> {
+        if keychains.len() != self.owners.len() {
+            return Err(Error::ApiError(format!(
+                "Expected {} keychains, got {}",
+                self.owners.len(),
+                keychains.len()
+            )));
+        }
+
+        for (owner, keychain) in self.owners.iter().zip(keychains) {
+            results
+                .get_mut(*owner)
+                .ok_or_else(|| Error::ApiError("Keychain request index out of range".to_owned()))?
+                .push(keychain);
+        }
+
+        Ok(())
+    }
+}
+
+/// Keychains of each request of a batched fetch, or the error of the first batch of a request
+/// that failed.
+pub(crate) struct BatchResults {
+    keychains: Vec<Vec<Vec<KeychainFeaturePoint>>>,
+    errors: Vec<Option<Error>>,
+}
+
+impl BatchResults {
+    pub fn new(len: usize) -> Self {
+        BatchResults {
+            keychains: vec![Vec::new(); len],
+            errors: (0..len).map(|_| None).collect(),
+        }
+    }
+
+    /// Adds the API result of a batch. An error fails every request of the batch.
+    pub fn add(&mut self, batch: &KeychainsBatch, result: Result<Vec<Vec<KeychainFeaturePoint>>>) {
+        let result = result.and_then(|keychains| batch.split(keychains, &mut self.keychains));
+
+        if let Err(e) = result {
+            for owner in &batch.owners {
+                if let Some(error @ None) = self.errors.get_mut(*owner) {
+                    *error = Some(copy_error(&e));
+                }
+            }
+        }
+    }
+
+    pub fn into_results(self) -> Vec<Result<Vec<Vec<KeychainFeaturePoint>>>> {
+        self.keychains
+            .into_iter()
+            .zip(self.errors)
+            .map(|(keychains, error)| match error {
+                Some(e) => Err(e),
+                None => Ok(keychains),
+            })
+            .collect()
+    }
+}
+
+/// Copies an error of a batch for each request of the batch.
+///
+/// API errors are copied as is, other errors are kept as their message.
+fn copy_error(error: &Error) -> Error {
+    match error {
+        Error::ApiKeyError => Error::ApiKeyError,
+        Error::ApiError(msg) => Error::ApiError(msg.clone()),
+        Error::ApiResponse(code, msg) => Error::ApiResponse(*code, msg.clone()),
+        Error::RateLimited => Error::RateLimited,
+        Error::ServerUnavailable(status) => Error::ServerUnavailable(*status),
+        Error::NetworkRequestStatus(status) => Error::NetworkRequestStatus(*status),
+        Error::NetworkConnection => Error::NetworkConnection,
+        e => Error::ApiError(e.to_string()),
+    }
+}
+
+impl KeychainsRequest {
+    /// Sends several requests to the keychain API, merged into as few calls as possible.
+    ///
+    /// Calls go through a `KeychainClient` with its default retry policy. A failed call only
+    /// fails the requests it holds keychains of.
+    ///
+    /// This method is only available for non-WASM targets.
+    ///
+    /// # Arguments
+    ///
+    /// * `requests` - The requests to send, usually built from several logs.
+    /// * `api_key` - The API key for authentication.
+    /// * `endpoint` - The URL endpoint for the API.
+    /// * `max_batch_size` - The maximum number of keychains sent in a single call.
+    ///
+    /// # Returns
+    ///
+    /// A Result for each request, in the order of `requests`, containing its keychains on success,
+    /// or the Error of the call that failed.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn fetch_batch(
+        requests: &[KeychainsRequest],
+        api_key: &str,
+        endpoint: Option<&str>,
+        max_batch_size: usize,
+    ) -> Vec<Result<Vec<Vec<KeychainFeaturePoint>>>> {
+        KeychainClient::new(api_key)
+            .with_endpoint(endpoint.unwrap_or(DEFAULT_ENDPOINT))
+            .fetch_batch(requests, max_batch_size)
+    }
+
+    /// Sends several requests asynchronously to the keychain API, merged into as few calls as possible.
+    ///
+    /// On native targets, calls go through a `KeychainClient` with its default retry policy.
+    /// A failed call only fails the requests it holds keychains of.
+    ///
+    /// This method is available for both WASM and non-WASM targets behind the `native-async` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `requests` - The requests to send, usually built from several logs.
+    /// * `api_key` - The API key for authentication.
+    /// * `endpoint` - The URL endpoint for the API.
+    /// * `max_batch_size` - The maximum number of keychains sent in a single call.
+    ///
+    /// # Returns
+    ///
+    /// A Future that resolves to a Result for each request, in the order of `requests`, containing
+    /// its keychains on success, or the Error of the call that failed.
+    #[cfg(any(target_arch = "wasm32", feature = "native-async"))]
+    pub async fn fetch_batch_async(
+        requests: &[KeychainsRequest],
+        api_key: &str,
+        endpoint: Option<&str>,
+        max_batch_size: usize,
+    ) -> Vec<Result<Vec<Vec<KeychainFeaturePoint>>>> {
+        #[cfg(not(target_arch = "wasm32"))]
+        let client =
+            KeychainClient::new(api_key).with_endpoint(endpoint.unwrap_or(DEFAULT_ENDPOINT));
+        let mut results = BatchResults::new(requests.len());
+
+        for batch in KeychainsBatch::from_requests(requests, max_batch_size) {
+            #[cfg(not(target_arch = "wasm32"))]
+            let result = client.fetch_async(&batch.request).await;
+            #[cfg(target_arch = "wasm32")]
+            let result = batch.request.fetch_async(api_key, endpoint).await;
+
+            results.add(&batch, result);
+        }
+
+        results.into_results()
+    }
+}
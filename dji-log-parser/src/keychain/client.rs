@@ -0,0 +1,202 @@
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::{Error, Result};
+
+use super::api::{native, DEFAULT_ENDPOINT};
+use super::batch::BatchResults;
+use super::{KeychainFeaturePoint, KeychainsBatch, KeychainsRequest};
+
+/// Keychain API client with retries and client-side rate limiting.
+///
+/// Requests failing with a rate limit (HTTP 429), a server error (HTTP 5xx) or a server error
+/// result code are retried with an exponential backoff. Consecutive requests sent through the
+/// same client, or its clones, are spaced by a minimum interval.
+///
+/// This client is only available for non-WASM targets.
+///
+/// # Example
+///
+/// ```no_run
+/// # use std::time::Duration;
+/// # use dji_log_parser::keychain::KeychainClient;
+/// # use dji_log_parser::DJILog;
+/// # let parser = DJILog::from_bytes(std::fs::read("path/to/log.txt")?)?;
+/// let client = KeychainClient::new("__DJI_API_KEY__")
+///     .with_retries(5, Duration::from_secs(1))
+///     .with_min_interval(Duration::from_millis(500));
+///
+/// let keychains = client.fetch(&parser.keychains_request()?)?;
+/// # Ok::<(), dji_log_parser::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct KeychainClient {
+    api_key: String,
+    endpoint: String,
+    max_retries: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    min_interval: Duration,
+    last_request: Arc<Mutex<Option<Instant>>>,
+}
+
+impl KeychainClient {
+    /// Creates a client for the DJI keychain API.
+    ///
+    /// By default, failed requests are retried 3 times starting with a 1 second backoff,
+    /// and requests are not rate limited.
+    ///
+    /// # Arguments
+    ///
+    /// * `api_key` - The API key for authentication.
+    pub fn new(api_key: &str) -> Self {
+        KeychainClient {
+            api_key: api_key.to_owned(),
+            endpoint: DEFAULT_ENDPOINT.to_owned(),
+            max_retries: 3,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+            min_interval: Duration::ZERO,
+            last_request: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Sets the URL endpoint for the API.
+    pub fn with_endpoint(mut self, endpoint: &str) -> Self {
+        self.endpoint = endpoint.to_owned();
+        self
+    }
+
+    /// Sets the retry policy.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_retries` - The maximum number of retries after the first attempt. `0` disables retries.
+    /// * `initial_backoff` - The delay before the first retry. The delay doubles on each retry.
+    pub fn with_retries(mut self, max_retries: u32, initial_backoff: Duration) -> Self {
+        self.max_retries = max_retries;
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Sets the maximum delay between two retries.
+    pub fn with_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Sets the minimum interval between two requests sent through this client.
+    pub fn with_min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    /// Sends a request to the keychain API, retrying on rate limit and server errors.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The keychains request to send.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing a vector of vectors of KeychainFeaturePoint on success,
+    /// or the Error of the last attempt on failure.
+    pub fn fetch(&self, request: &KeychainsRequest) -> Result<Vec<Vec<KeychainFeaturePoint>>> {
+        let mut backoff = self.initial_backoff;
+        let mut retries = 0;
+
+        loop {
+            self.wait_rate_limit();
+
+            match native::fetch(&self.api_key, &self.endpoint, request) {
+                Err(e) if retries < self.max_retries && is_retryable(&e) => {
+                    thread::sleep(backoff);
+                    backoff = (backoff * 2).min(self.max_backoff);
+                    retries += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Sends a request to the keychain API asynchronously, retrying on rate limit and server errors.
+    ///
+    /// The request and its retries run on a separate thread. This method is only available
+    /// behind the `native-async` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The keychains request to send.
+    ///
+    /// # Returns
+    ///
+    /// A Future that resolves to a Result containing a vector of vectors of KeychainFeaturePoint
+    /// on success, or the Error of the last attempt on failure.
+    #[cfg(feature = "native-async")]
+    pub async fn fetch_async(
+        &self,
+        request: &KeychainsRequest,
+    ) -> Result<Vec<Vec<KeychainFeaturePoint>>> {
+        let client = self.clone();
+        let request = request.clone();
+
+        let (tx, rx) = async_channel::bounded(1);
+
+        thread::spawn(move || {
+            let _ = tx.send_blocking(client.fetch(&request));
+        });
+
+        rx.recv().await.map_err(|_| Error::NetworkConnection)?
+    }
+
+    /// Sends several requests to the keychain API, merged into as few calls as possible.
+    ///
+    /// See `KeychainsBatch::from_requests` for how requests are merged. Each call is retried
+    /// like `fetch`, and a failed call only fails the requests it holds keychains of.
+    ///
+    /// # Arguments
+    ///
+    /// * `requests` - The requests to send, usually built from several logs.
+    /// * `max_batch_size` - The maximum number of keychains sent in a single call.
+    ///
+    /// # Returns
+    ///
+    /// A Result for each request, in the order of `requests`, containing its keychains on success,
+    /// or the Error of the call that failed.
+    pub fn fetch_batch(
+        &self,
+        requests: &[KeychainsRequest],
+        max_batch_size: usize,
+    ) -> Vec<Result<Vec<Vec<KeychainFeaturePoint>>>> {
+        let mut results = BatchResults::new(requests.len());
+
+        for batch in KeychainsBatch::from_requests(requests, max_batch_size) {
+            results.add(&batch, self.fetch(&batch.request));
+        }
+
+        results.into_results()
+    }
+
+    /// Waits until the minimum interval since the previous request has elapsed.
+    fn wait_rate_limit(&self) {
+        let mut last_request = match self.last_request.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+
+        if let Some(last) = *last_request {
+            let elapsed = last.elapsed();
+            if elapsed < self.min_interval {
+                thread::sleep(self.min_interval - elapsed);
+            }
+        }
+
+        *last_request = Some(Instant::now());
+    }
+}
+
+/// Checks if a failed request may succeed when sent again.
+fn is_retryable(error: &Error) -> bool {
+    matches!(error, Error::RateLimited | Error::ServerUnavailable(_))
+}
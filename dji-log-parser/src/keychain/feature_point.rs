@@ -1,12 +1,12 @@
-use binrw::binread;
+use binrw::binrw;
 #[cfg(target_arch = "wasm32")]
 use tsify_next::Tsify;
 
 use serde::{Deserialize, Serialize, Serializer};
 
-#[binread]
+#[binrw]
 #[derive(Hash, Eq, PartialEq, Debug, Clone, Copy)]
-#[br(repr(u16))]
+#[brw(repr(u16))]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub enum FeaturePoint {
     BaseFeature = 1,
@@ -5,13 +5,25 @@ use std::collections::HashMap;
 #[cfg(target_arch = "wasm32")]
 use tsify_next::Tsify;
 
+use crate::report::DecryptionStats;
+
 mod api;
+mod batch;
+#[cfg(not(target_arch = "wasm32"))]
+mod client;
 mod feature_point;
+mod store;
 
 pub use api::*;
+pub use batch::KeychainsBatch;
+#[cfg(not(target_arch = "wasm32"))]
+pub use client::KeychainClient;
 pub use feature_point::FeaturePoint;
+#[cfg(not(target_arch = "wasm32"))]
+pub use store::FileKeychainStore;
+pub use store::{KeychainStore, MemoryKeychainStore};
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub struct EncodedKeychainFeaturePoint {
@@ -28,19 +40,54 @@ pub struct KeychainFeaturePoint {
     pub aes_iv: String,
 }
 
+/// Result of the validation of a keychain entry, see `DJILog::validate_keychains`.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(target_arch = "wasm32", derive(Tsify))]
+pub enum KeychainStatus {
+    /// The sampled records of the feature point were decrypted
+    Valid,
+    /// Some sampled records of the feature point failed to decrypt
+    Invalid,
+    /// No encrypted record of the feature point was found
+    Untested,
+}
+
+/// AES IV and key of a feature point
+type KeychainEntry = (Vec<u8>, Vec<u8>);
+
 /// `Keychain` serves as more convenient access to decrypt `Record` instances.
 /// It associates each `FeaturePoint` with its corresponding AES initialization vector (IV)
 /// and encryption key. In this hashmap, each `FeaturePoint` is linked to a tuple containing
 /// the AES IV and key as array of bytes.
-pub(crate) struct Keychain(HashMap<FeaturePoint, (Vec<u8>, Vec<u8>)>);
+///
+/// The keychain also counts the records decrypted, or failing to decrypt, with each entry.
+#[derive(Clone)]
+pub(crate) struct Keychain {
+    entries: HashMap<FeaturePoint, KeychainEntry>,
+    /// Offset of the last decrypted record and the entry used to decrypt it. A record may be
+    /// decoded several times while its layouts are tried, each attempt reuses this entry.
+    last_record: Option<(u64, FeaturePoint, KeychainEntry)>,
+    /// `true` if the last decrypted record failed the AES padding check
+    last_record_failed: bool,
+    stats: HashMap<FeaturePoint, DecryptionStats>,
+}
 
 impl Keychain {
     pub fn empty() -> Self {
-        Keychain(HashMap::new())
+        Keychain::new(HashMap::new())
+    }
+
+    fn new(entries: HashMap<FeaturePoint, KeychainEntry>) -> Self {
+        Keychain {
+            entries,
+            last_record: None,
+            last_record_failed: false,
+            stats: HashMap::new(),
+        }
     }
 
     pub fn from_feature_points(keychain_entries: &Vec<KeychainFeaturePoint>) -> Self {
-        Keychain(
+        Keychain::new(
             keychain_entries
                 .into_iter()
                 .map(|entry| {
@@ -56,15 +103,62 @@ impl Keychain {
         )
     }
 
-    pub fn get(&self, key: &FeaturePoint) -> Option<&(Vec<u8>, Vec<u8>)> {
-        self.0.get(key)
+    /// Returns `true` if the keychain has an entry for the feature point.
+    pub fn contains(&self, feature_point: FeaturePoint) -> bool {
+        self.entries.contains_key(&feature_point)
     }
 
-    pub fn insert(
+    /// Returns the AES IV and key to decrypt the record at `offset`.
+    ///
+    /// The second value is `true` when the record was already decrypted, in which case the IV
+    /// it was first decrypted with is returned and the keychain must not be updated again.
+    pub fn record_entry(
         &mut self,
-        key: FeaturePoint,
-        value: (Vec<u8>, Vec<u8>),
-    ) -> Option<(Vec<u8>, Vec<u8>)> {
-        self.0.insert(key, value)
+        offset: u64,
+        feature_point: FeaturePoint,
+    ) -> Option<(KeychainEntry, bool)> {
+        if let Some((last_offset, last_feature_point, entry)) = &self.last_record {
+            if *last_offset == offset && *last_feature_point == feature_point {
+                return Some((entry.clone(), true));
+            }
+        }
+
+        let entry = self.entries.get(&feature_point)?.clone();
+        self.last_record = Some((offset, feature_point, entry.clone()));
+        self.last_record_failed = false;
+        Some((entry, false))
+    }
+
+    /// Returns `true` if the record at `offset` was the last one decrypted, and failed the AES
+    /// padding check.
+    pub fn is_failed_record(&self, offset: u64) -> bool {
+        self.last_record_failed
+            && matches!(self.last_record, Some((last_offset, _, _)) if last_offset == offset)
+    }
+
+    /// Chains the IV of a feature point to the next record, and counts the decryption result.
+    pub fn update_record_entry(
+        &mut self,
+        feature_point: FeaturePoint,
+        next_iv: Vec<u8>,
+        is_decrypted: bool,
+    ) {
+        if let Some(entry) = self.entries.get_mut(&feature_point) {
+            entry.0 = next_iv;
+        }
+
+        self.last_record_failed = !is_decrypted;
+
+        let stats = self.stats.entry(feature_point).or_default();
+        if is_decrypted {
+            stats.decrypted += 1;
+        } else {
+            stats.failed += 1;
+        }
+    }
+
+    /// Returns the decryption counts collected since the last call, per feature point.
+    pub fn take_stats(&mut self) -> HashMap<FeaturePoint, DecryptionStats> {
+        std::mem::take(&mut self.stats)
     }
 }
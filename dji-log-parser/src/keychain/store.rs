@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+#[cfg(not(target_arch = "wasm32"))]
+use std::fs;
+#[cfg(not(target_arch = "wasm32"))]
+use std::io::ErrorKind;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::PathBuf;
+
+use crate::Result;
+
+use super::KeychainFeaturePoint;
+
+/// Storage for keychains retrieved from the DJI API.
+///
+/// Entries are keyed by the fingerprint of the `KeychainsRequest` built from a log
+/// (see `KeychainsRequest::fingerprint`), so that keychains can be reused across runs
+/// without calling the DJI API again.
+pub trait KeychainStore {
+    /// Returns the keychains stored for the given fingerprint, if any.
+    fn get(&self, fingerprint: &str) -> Result<Option<Vec<Vec<KeychainFeaturePoint>>>>;
+
+    /// Stores the keychains for the given fingerprint, replacing any existing entry.
+    fn put(&mut self, fingerprint: &str, keychains: &[Vec<KeychainFeaturePoint>]) -> Result<()>;
+}
+
+/// In-memory `KeychainStore`, useful to share keychains within a single process.
+#[derive(Debug, Default, Clone)]
+pub struct MemoryKeychainStore(HashMap<String, Vec<Vec<KeychainFeaturePoint>>>);
+
+impl MemoryKeychainStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl KeychainStore for MemoryKeychainStore {
+    fn get(&self, fingerprint: &str) -> Result<Option<Vec<Vec<KeychainFeaturePoint>>>> {
+        Ok(self.0.get(fingerprint).cloned())
+    }
+
+    fn put(&mut self, fingerprint: &str, keychains: &[Vec<KeychainFeaturePoint>]) -> Result<()> {
+        self.0.insert(fingerprint.to_owned(), keychains.to_vec());
+        Ok(())
+    }
+}
+
+/// Filesystem `KeychainStore` writing one `<fingerprint>.json` file per entry in a directory.
+///
+/// This store is only available for non-WASM targets.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone)]
+pub struct FileKeychainStore {
+    directory: PathBuf,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FileKeychainStore {
+    /// Creates a store in the given directory. The directory is created on first write.
+    ///
+    /// # Arguments
+    ///
+    /// * `directory` - Path of the directory holding the keychain files.
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        FileKeychainStore {
+            directory: directory.into(),
+        }
+    }
+
+    fn path(&self, fingerprint: &str) -> PathBuf {
+        self.directory.join(format!("{}.json", fingerprint))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl KeychainStore for FileKeychainStore {
+    fn get(&self, fingerprint: &str) -> Result<Option<Vec<Vec<KeychainFeaturePoint>>>> {
+        match fs::read(self.path(fingerprint)) {
+            Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn put(&mut self, fingerprint: &str, keychains: &[Vec<KeychainFeaturePoint>]) -> Result<()> {
+        fs::create_dir_all(&self.directory)?;
+        fs::write(self.path(fingerprint), serde_json::to_vec(keychains)?)?;
+        Ok(())
+    }
+}
@@ -1,18 +1,22 @@
 use binrw::binread;
 use serde::Serialize;
 
-use crate::decoder::XorDecoder;
+use crate::decoder::xor_decoder;
 
+/// Auxiliary blocks, written between the Prefix and the records section of logs version 13
+/// and above.
 #[binread]
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[br(little)]
-pub(crate) enum Auxiliary {
+pub enum Auxiliary {
+    /// Xor encoded details and signature
     #[br(magic = 0u8)]
     Info(
         #[br(temp)] u16,
-        #[br(pad_size_to = self_0, map_stream = |reader| XorDecoder::new(reader, 0))] AuxiliaryInfo,
+        #[br(pad_size_to = self_0, map_stream = |reader| xor_decoder(reader, 0))] AuxiliaryInfo,
     ),
 
+    /// Version and department used to request keychains
     #[br(magic = 1u8)]
     Version(
         #[br(temp)] u16,
@@ -21,12 +25,13 @@ pub(crate) enum Auxiliary {
 }
 
 #[binread]
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[br(little)]
-pub(crate) struct AuxiliaryInfo {
+pub struct AuxiliaryInfo {
     pub version_data: u8,
     #[br(temp)]
     info_length: u16,
+    /// Details block, decoded as `Details`
     #[br(count = info_length)]
     pub info_data: Vec<u8>,
     #[br(temp)]
@@ -36,9 +41,9 @@ pub(crate) struct AuxiliaryInfo {
 }
 
 #[binread]
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[br(little)]
-pub(crate) struct AuxiliaryVersion {
+pub struct AuxiliaryVersion {
     pub version: u16,
     #[br(map = |x: u8| Department::from(x))]
     pub department: Department,
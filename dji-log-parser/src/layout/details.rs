@@ -1,23 +1,29 @@
-use binrw::binread;
+use binrw::binrw;
 use chrono::{DateTime, Utc};
 use serde::Serialize;
 use std::io::SeekFrom;
 #[cfg(target_arch = "wasm32")]
 use tsify_next::Tsify;
 
-#[binread]
-#[derive(Debug, Serialize, Clone)]
+use crate::utils::{string_to_bytes, version_to_bytes};
+
+#[binrw]
+#[derive(Debug, Serialize, Clone, Default)]
 #[serde(rename_all = "camelCase")]
-#[br(little, import(version: u8))]
+#[brw(little, import(version: u8))]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub struct Details {
     #[br(count=20, map = |s: Vec<u8>| String::from_utf8_lossy(&s).trim_end_matches('\0').to_string())]
+    #[bw(map = |s: &String| string_to_bytes(s, 20))]
     pub sub_street: String,
     #[br(count=20, map = |s: Vec<u8>| String::from_utf8_lossy(&s).trim_end_matches('\0').to_string())]
+    #[bw(map = |s: &String| string_to_bytes(s, 20))]
     pub street: String,
     #[br(count=20, map = |s: Vec<u8>| String::from_utf8_lossy(&s).trim_end_matches('\0').to_string())]
+    #[bw(map = |s: &String| string_to_bytes(s, 20))]
     pub city: String,
     #[br(count=20, map = |s: Vec<u8>| String::from_utf8_lossy(&s).trim_end_matches('\0').to_string())]
+    #[bw(map = |s: &String| string_to_bytes(s, 20))]
     pub area: String,
     pub is_favorite: u8,
     pub is_new: u8,
@@ -26,6 +32,7 @@ pub struct Details {
     pub detail_info_checksum: i32,
     #[cfg_attr(target_arch = "wasm32", tsify(type = "string"))]
     #[br(map = |x: i64| DateTime::from_timestamp(x / 1000, (x % 1000 * 1000000) as u32).unwrap_or_default())]
+    #[bw(map = |x: &DateTime<Utc>| x.timestamp_millis())]
     pub start_time: DateTime<Utc>,
     /// degrees
     pub longitude: f64,
@@ -35,6 +42,7 @@ pub struct Details {
     pub total_distance: f32,
     /// seconds
     #[br(map = |x: i32| x as f64 / 1000.0)]
+    #[bw(map = |x: &f64| (x * 1000.0).round() as i32)]
     pub total_time: f64,
     /// meters
     pub max_height: f32,
@@ -48,48 +56,74 @@ pub struct Details {
     pub moment_pic_shrink_image_buffer_len: [i32; 4],
     /// degrees
     #[br(map = |v: [f64; 4]| v.map(|rad: f64| rad.to_degrees()) )]
+    #[bw(map = |v: &[f64; 4]| v.map(|deg: f64| deg.to_radians()))]
     pub moment_pic_longitude: [f64; 4],
     /// degrees
     #[br(map = |v: [f64; 4]| v.map(|rad: f64| rad.to_degrees()) )]
+    #[bw(map = |v: &[f64; 4]| v.map(|deg: f64| deg.to_radians()))]
     pub moment_pic_latitude: [f64; 4],
     #[br(temp)]
+    #[bw(calc = 0)]
     _analysis_offset: i64,
     #[br(temp)]
+    #[bw(calc = [0; 16])]
     _user_api_center_id_md5: [u8; 16],
-    #[br(seek_before = if version <= 5 { SeekFrom::Start(352) } else { SeekFrom::Current(0) })]
+    #[brw(seek_before = if version <= 5 { SeekFrom::Start(352) } else { SeekFrom::Current(0) })]
     pub take_off_altitude: f32,
     #[br(
         seek_before = if version <= 5 { SeekFrom::Start(277) } else { SeekFrom::Current(0) },
         map = |x: u8| ProductType::from(x))
     ]
+    #[bw(
+        seek_before = if version <= 5 { SeekFrom::Start(277) } else { SeekFrom::Current(0) },
+        map = |x: &ProductType| u8::from(*x))
+    ]
     pub product_type: ProductType,
     #[br(temp)]
+    #[bw(calc = 0)]
     _activation_timestamp: i64,
     #[br(
         seek_before = if version <= 5 { SeekFrom::Start(278) } else { SeekFrom::Current(0) },
         count = if version <= 5 { 24 } else { 32 }, map = |s: Vec<u8>| String::from_utf8_lossy(&s).trim_end_matches('\0').to_string()
     )]
+    #[bw(
+        seek_before = if version <= 5 { SeekFrom::Start(278) } else { SeekFrom::Current(0) },
+        map = |s: &String| string_to_bytes(s, if version <= 5 { 24 } else { 32 })
+    )]
     pub aircraft_name: String,
     #[br(
         seek_before = if version <= 5 { SeekFrom::Start(267) } else { SeekFrom::Current(0) },
         count = if version <= 5 { 10 } else { 16 }, map = |s: Vec<u8>| String::from_utf8_lossy(&s).trim_end_matches('\0').to_string()
     )]
+    #[bw(
+        seek_before = if version <= 5 { SeekFrom::Start(267) } else { SeekFrom::Current(0) },
+        map = |s: &String| string_to_bytes(s, if version <= 5 { 10 } else { 16 })
+    )]
     pub aircraft_sn: String,
     #[br(
         seek_before = if version <= 5 { SeekFrom::Start(318) } else { SeekFrom::Current(0) },
         count = if version <= 5 { 10 } else { 16 }, map = |s: Vec<u8>| String::from_utf8_lossy(&s).trim_end_matches('\0').to_string()
     )]
+    #[bw(
+        seek_before = if version <= 5 { SeekFrom::Start(318) } else { SeekFrom::Current(0) },
+        map = |s: &String| string_to_bytes(s, if version <= 5 { 10 } else { 16 })
+    )]
     pub camera_sn: String,
     #[br(count = if version <= 5 { 10 } else { 16 }, map = |s: Vec<u8>| String::from_utf8_lossy(&s).trim_end_matches('\0').to_string())]
+    #[bw(map = |s: &String| string_to_bytes(s, if version <= 5 { 10 } else { 16 }))]
     pub rc_sn: String,
     #[br(count = if version <= 5 { 10 } else { 16 })]
     #[br(temp)]
+    #[bw(calc = encode_battery_sn(ProductType::from(product_type), battery_sn, if version <= 5 { 10 } else { 16 }))]
     battery_buf: Vec<u8>,
     #[br(calc = parse_battery_sn(product_type, battery_buf))]
+    #[bw(ignore)]
     pub battery_sn: String,
     #[br(map = |x: u8| Platform::from(x))]
+    #[bw(map = |x: &Platform| u8::from(*x))]
     pub app_platform: Platform,
     #[br(map = |x: [u8; 3]| format!("{}.{}.{}", x[0], x[1], x[2]))]
+    #[bw(map = |x: &String| version_to_bytes(x, 3))]
     pub app_version: String,
 }
 
@@ -229,6 +263,74 @@ impl From<u8> for ProductType {
     }
 }
 
+impl From<ProductType> for u8 {
+    fn from(value: ProductType) -> Self {
+        match value {
+            ProductType::None => 0,
+            ProductType::Inspire1 => 1,
+            ProductType::Phantom3Standard => 2,
+            ProductType::Phantom3Advanced => 3,
+            ProductType::Phantom3Pro => 4,
+            ProductType::OSMO => 5,
+            ProductType::Matrice100 => 6,
+            ProductType::Phantom4 => 7,
+            ProductType::LB2 => 8,
+            ProductType::Inspire1Pro => 9,
+            ProductType::A3 => 10,
+            ProductType::Matrice600 => 11,
+            ProductType::Phantom34K => 12,
+            ProductType::MavicPro => 13,
+            ProductType::ZenmuseXT => 14,
+            ProductType::Inspire1RAW => 15,
+            ProductType::A2 => 16,
+            ProductType::Inspire2 => 17,
+            ProductType::OSMOPro => 18,
+            ProductType::OSMORaw => 19,
+            ProductType::OSMOPlus => 20,
+            ProductType::Mavic => 21,
+            ProductType::OSMOMobile => 22,
+            ProductType::OrangeCV600 => 23,
+            ProductType::Phantom4Pro => 24,
+            ProductType::N3FC => 25,
+            ProductType::Spark => 26,
+            ProductType::Matrice600Pro => 27,
+            ProductType::Phantom4Advanced => 28,
+            ProductType::Phantom3SE => 29,
+            ProductType::AG405 => 30,
+            ProductType::Matrice200 => 31,
+            ProductType::Matrice210 => 33,
+            ProductType::Matrice210RTK => 34,
+            ProductType::MavicAir => 38,
+            ProductType::Mavic2 => 42,
+            ProductType::Phantom4ProV2 => 44,
+            ProductType::Phantom4RTK => 46,
+            ProductType::Phantom4Multispectral => 57,
+            ProductType::Mavic2Enterprise => 58,
+            ProductType::MavicMini => 59,
+            ProductType::Matrice200V2 => 60,
+            ProductType::Matrice210V2 => 61,
+            ProductType::Matrice210RTKV2 => 62,
+            ProductType::MavicAir2 => 67,
+            ProductType::Matrice300RTK => 70,
+            ProductType::FPV => 73,
+            ProductType::MavicAir2S => 75,
+            ProductType::Mini2 => 76,
+            ProductType::Mavic3 => 77,
+            ProductType::MiniSE => 96,
+            ProductType::Mini3Pro => 103,
+            ProductType::Mavic3Pro => 111,
+            ProductType::Mini2SE => 113,
+            ProductType::Matrice30 => 116,
+            ProductType::Mavic3Enterprise => 118,
+            ProductType::Avata => 121,
+            ProductType::Mini4Pro => 126,
+            ProductType::Avata2 => 152,
+            ProductType::Matrice350RTK => 170,
+            ProductType::Unknown(value) => value,
+        }
+    }
+}
+
 impl ProductType {
     pub fn battery_cell_num(&self) -> u8 {
         match self {
@@ -337,9 +439,10 @@ impl ProductType {
     }
 }
 
-#[derive(Serialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Debug, Clone, PartialEq, Copy, Default)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub enum Platform {
+    #[default]
     IOS,
     Android,
     DJIFly,
@@ -364,6 +467,20 @@ impl From<u8> for Platform {
     }
 }
 
+impl From<Platform> for u8 {
+    fn from(value: Platform) -> Self {
+        match value {
+            Platform::IOS => 1,
+            Platform::Android => 2,
+            Platform::DJIFly => 6,
+            Platform::Windows => 10,
+            Platform::Mac => 11,
+            Platform::Linux => 12,
+            Platform::Unknown(value) => value,
+        }
+    }
+}
+
 /// Decode the battery serial number from raw bytes, choosing the method based on model:
 /// - For Inspire1/Pro/RAW, interpret the low nibble of each byte as a BCD digit,
 ///   reverse the sequence, and trim leading `'0'`.
@@ -395,3 +512,28 @@ fn decode_reversed_bcd_battery_sn(buf: Vec<u8>) -> String {
         .skip_while(|&c| c == '0')
         .collect()
 }
+
+/// Encode a battery serial number into raw bytes of the given length, the inverse of
+/// `parse_battery_sn`:
+/// - For Inspire1/Pro/RAW, write each digit as a byte in reversed order, padded with zeros.
+/// - Otherwise, write the string as UTF-8, null padded.
+///
+pub fn encode_battery_sn(product_type: ProductType, battery_sn: &str, length: usize) -> Vec<u8> {
+    const BCD_PRODUCTS: [ProductType; 3] = [
+        ProductType::Inspire1,
+        ProductType::Inspire1Pro,
+        ProductType::Inspire1RAW,
+    ];
+
+    let mut buf: Vec<u8> = if BCD_PRODUCTS.contains(&product_type) {
+        battery_sn
+            .chars()
+            .rev()
+            .map(|c| c.to_digit(10).unwrap_or_default() as u8)
+            .collect()
+    } else {
+        battery_sn.as_bytes().to_vec()
+    };
+    buf.resize(length, 0);
+    buf
+}
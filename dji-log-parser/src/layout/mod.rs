@@ -1,3 +1,3 @@
 pub mod auxiliary;
 pub mod details;
-pub(crate) mod prefix;
+pub mod prefix;
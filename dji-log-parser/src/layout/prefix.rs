@@ -1,30 +1,64 @@
-use binrw::binread;
+use binrw::binrw;
 
 // Constants
 const OLD_PREFIX_SIZE: u64 = 12;
 const PREFIX_SIZE: u64 = 100;
+/// Size of the details block of v12 logs, written right after the prefix
+pub(crate) const INFO_SIZE_V12: u64 = 436;
 
-#[binread]
+/// Header block at the start of the log file, locating the Details and the records.
+#[binrw]
 #[derive(Debug, Clone)]
-#[br(little)]
+#[brw(little)]
 pub struct Prefix {
     detail_offset: u64,
-    _detail_length: u16,
+    detail_length: u16,
     pub version: u8,
     #[br(temp)]
+    #[bw(calc = 0)]
     _unknown: u8,
     #[br(temp)]
+    #[bw(calc = 0)]
     _encrypt_magic_version: u64,
     #[br(temp)]
+    #[bw(calc = [0; 80])]
     _reserved: [u8; 80],
 }
 
 impl Prefix {
+    pub(crate) fn new(version: u8) -> Self {
+        Prefix {
+            detail_offset: 0,
+            detail_length: 0,
+            version,
+        }
+    }
+
+    pub(crate) fn set_detail(&mut self, detail_offset: u64, detail_length: u16) {
+        self.detail_offset = detail_offset;
+        self.detail_length = detail_length;
+    }
+
+    /// Size of the prefix block, as written at the start of the log file
+    pub fn size(&self) -> u64 {
+        if self.version < 6 {
+            OLD_PREFIX_SIZE
+        } else {
+            PREFIX_SIZE
+        }
+    }
+
     pub(crate) fn recover_detail_offset(&mut self, detail_offset: u64) {
         self.detail_offset = detail_offset;
     }
 
-    pub(crate) fn detail_offset(&self) -> u64 {
+    /// Length of the Details block, as written in the prefix
+    pub fn detail_length(&self) -> u16 {
+        self.detail_length
+    }
+
+    /// Offset of the Details block, or of the Auxiliary blocks for logs version 13 and above
+    pub fn detail_offset(&self) -> u64 {
         if self.version < 12 {
             self.detail_offset
         } else {
@@ -32,19 +66,21 @@ impl Prefix {
         }
     }
 
-    pub(crate) fn records_offset(&self) -> u64 {
+    /// Offset of the first record
+    pub fn records_offset(&self) -> u64 {
         if self.version < 6 {
             OLD_PREFIX_SIZE
         } else if self.version < 12 {
             PREFIX_SIZE
         } else if self.version == 12 {
-            PREFIX_SIZE + 436 // We manually add info size
+            PREFIX_SIZE + INFO_SIZE_V12 // We manually add info size
         } else {
             self.detail_offset
         }
     }
 
-    pub(crate) fn records_end_offset(&self, file_size: impl Into<u64>) -> u64 {
+    /// Offset of the end of the records section
+    pub fn records_end_offset(&self, file_size: impl Into<u64>) -> u64 {
         if self.version < 12 {
             self.detail_offset
         } else {
@@ -23,32 +23,116 @@
 //!
 //! ### Initialization
 //! Initialize a `DJILog` instance from a byte slice to access version information and metadata:
-//! ```
+//! ```no_run
+//! # use dji_log_parser::DJILog;
+//! # let bytes = std::fs::read("path/to/log.txt")?;
 //! let parser = DJILog::from_bytes(bytes).unwrap();
+//! # Ok::<(), dji_log_parser::Error>(())
+//! ```
+//!
+//! Or from any reader implementing `Read` and `Seek`, to avoid loading the whole file in memory:
+//!
+//! ```no_run
+//! # use std::fs::File;
+//! # use dji_log_parser::DJILog;
+//! # let path = "path/to/log.txt";
+//! let parser = DJILog::from_reader(File::open(path)?).unwrap();
+//! # Ok::<(), dji_log_parser::Error>(())
 //! ```
 //!
 //! ### Access general data
 //!
 //! General data are not encrypted and can be accessed from the parser for all log versions:
 //!
-//! ```
+//! ```no_run
+//! # use dji_log_parser::DJILog;
+//! # let parser = DJILog::from_bytes(std::fs::read("path/to/log.txt")?)?;
 //! // Print the log version
 //! println!("Version: {:?}", parser.version);
 //!
 //! // Print the log details section
-//! println!("Details: {}", parser.details);
+//! println!("Details: {:?}", parser.details);
+//! # Ok::<(), dji_log_parser::Error>(())
+//! ```
+//!
+//! ### Probe logs
+//!
+//! To index many logs, the version, details, and for logs version 13 and later the department and
+//! auxiliary version, can be read without decoding any record:
+//!
+//! ```no_run
+//! # use std::fs::File;
+//! # use dji_log_parser::DJILog;
+//! # let path = "path/to/log.txt";
+//! let summary = DJILog::probe(File::open(path)?)?;
+//! println!("Aircraft: {:?} {}", summary.product_type, summary.aircraft_sn);
+//! # Ok::<(), dji_log_parser::Error>(())
+//! ```
+//!
+//! ### Inspect the layout
+//!
+//! The Prefix, the Auxiliary blocks and the offsets of the records section can be inspected, for
+//! example to study new log versions:
+//!
+//! ```no_run
+//! # use dji_log_parser::layout::auxiliary::Auxiliary;
+//! # use dji_log_parser::DJILog;
+//! # let parser = DJILog::from_bytes(std::fs::read("path/to/log.txt")?)?;
+//! println!("Records: {}..{}", parser.records_offset(), parser.records_end_offset());
+//! for auxiliary in parser.auxiliary()? {
+//!     if let Auxiliary::Info(info) = auxiliary {
+//!         println!("Signature: {:?}", info.signature_data);
+//!     }
+//! }
+//! # Ok::<(), dji_log_parser::Error>(())
 //! ```
 //!
 //! ### Retrieve keychains
 //!
 //! For logs version 13 and later, keychains must be retrieved from the DJI API to decode the records:
 //!
-//! ```
+//! ```no_run
+//! # use dji_log_parser::DJILog;
+//! # let parser = DJILog::from_bytes(std::fs::read("path/to/log.txt")?)?;
 //! // Replace `__DJI_API_KEY__` with your actual apiKey
 //! let keychains = parser.fetch_keychains("__DJI_API_KEY__").unwrap();
+//! # Ok::<(), dji_log_parser::Error>(())
 //! ```
 //!
 //! Keychains can be retrieved once, serialized, and stored along with the log file for future offline use.
+//! A `KeychainStore` can also be used to cache keychains and only call the DJI API for unknown logs:
+//!
+//! ```no_run
+//! # use dji_log_parser::keychain::FileKeychainStore;
+//! # use dji_log_parser::DJILog;
+//! # let parser = DJILog::from_bytes(std::fs::read("path/to/log.txt")?)?;
+//! let mut store = FileKeychainStore::new("keychains");
+//! let keychains = parser.fetch_keychains_with_store("__DJI_API_KEY__", &mut store).unwrap();
+//! # Ok::<(), dji_log_parser::Error>(())
+//! ```
+//!
+//! To retry on rate limit and server errors, or to space requests, use a `KeychainClient`:
+//!
+//! ```no_run
+//! # use std::time::Duration;
+//! # use dji_log_parser::keychain::KeychainClient;
+//! # use dji_log_parser::DJILog;
+//! # let parser = DJILog::from_bytes(std::fs::read("path/to/log.txt")?)?;
+//! let client = KeychainClient::new("__DJI_API_KEY__").with_min_interval(Duration::from_secs(1));
+//! let keychains = client.fetch(&parser.keychains_request()?)?;
+//! # Ok::<(), dji_log_parser::Error>(())
+//! ```
+//!
+//! Wrong or stale keychains can be detected before a full parse, by decrypting the first records
+//! of each feature point:
+//!
+//! ```no_run
+//! # use dji_log_parser::DJILog;
+//! # let parser = DJILog::from_bytes(std::fs::read("path/to/log.txt")?)?;
+//! # let keychains = parser.fetch_keychains("__DJI_API_KEY__")?;
+//! let statuses = parser.validate_keychains(keychains.clone())?;
+//! # Ok::<(), dji_log_parser::Error>(())
+//! ```
 //!
 //! ### Accessing Frames
 //!
@@ -59,14 +143,21 @@
 //!
 //! For versions prior to 13:
 //!
-//! ```
+//! ```no_run
+//! # use dji_log_parser::DJILog;
+//! # let parser = DJILog::from_bytes(std::fs::read("path/to/log.txt")?)?;
 //! let frames = parser.frames(None);
+//! # Ok::<(), dji_log_parser::Error>(())
 //! ```
 //!
 //! For version 13 and later:
 //!
-//! ```
+//! ```no_run
+//! # use dji_log_parser::DJILog;
+//! # let parser = DJILog::from_bytes(std::fs::read("path/to/log.txt")?)?;
+//! # let keychains = parser.fetch_keychains("__DJI_API_KEY__")?;
 //! let frames = parser.frames(Some(keychains));
+//! # Ok::<(), dji_log_parser::Error>(())
 //! ```
 //!
 //! ### Accessing raw Records
@@ -74,14 +165,160 @@
 //! Decrypt raw records based on the log file version.
 //! For versions prior to 13:
 //!
-//! ```
+//! ```no_run
+//! # use dji_log_parser::DJILog;
+//! # let parser = DJILog::from_bytes(std::fs::read("path/to/log.txt")?)?;
 //! let records = parser.records(None);
+//! # Ok::<(), dji_log_parser::Error>(())
 //! ```
 //!
 //! For version 13 and later:
 //!
-//! ```
+//! ```no_run
+//! # use dji_log_parser::DJILog;
+//! # let parser = DJILog::from_bytes(std::fs::read("path/to/log.txt")?)?;
+//! # let keychains = parser.fetch_keychains("__DJI_API_KEY__")?;
 //! let records = parser.records(Some(keychains));
+//! # Ok::<(), dji_log_parser::Error>(())
+//! ```
+//!
+//! Records can also be decoded lazily, one at a time, to avoid loading the whole
+//! records section in memory:
+//!
+//! ```no_run
+//! # use dji_log_parser::DJILog;
+//! # let parser = DJILog::from_bytes(std::fs::read("path/to/log.txt")?)?;
+//! # let keychains = parser.fetch_keychains("__DJI_API_KEY__")?;
+//! for record in parser.record_iter(Some(keychains))? {
+//!     println!("{:?}", record?);
+//! }
+//! # Ok::<(), dji_log_parser::Error>(())
+//! ```
+//!
+//! Corrupted records stop the decoding by default. To skip over them and resume at the next
+//! plausible record, enable the resync recovery mode:
+//!
+//! ```no_run
+//! # use dji_log_parser::RecoveryMode;
+//! # use dji_log_parser::DJILog;
+//! # let parser = DJILog::from_bytes(std::fs::read("path/to/log.txt")?)?;
+//! # let keychains = parser.fetch_keychains("__DJI_API_KEY__")?;
+//! let mut records = parser.record_iter(Some(keychains))?.with_recovery(RecoveryMode::Resync);
+//! for record in records.by_ref() {
+//!     println!("{:?}", record?);
+//! }
+//! println!("{:?}", records.report());
+//! # Ok::<(), dji_log_parser::Error>(())
+//! ```
+//!
+//! To study record layouts, records can also be decoded along with their offset, declared length and
+//! decrypted bytes:
+//!
+//! ```no_run
+//! # use dji_log_parser::DJILog;
+//! # let parser = DJILog::from_bytes(std::fs::read("path/to/log.txt")?)?;
+//! # let keychains = parser.fetch_keychains("__DJI_API_KEY__")?;
+//! for raw_record in parser.raw_record_iter(Some(keychains))? {
+//!     let raw_record = raw_record?;
+//!     println!("{} {:?} {:02x?}", raw_record.offset, raw_record.record_type, raw_record.decrypted);
+//! }
+//! # Ok::<(), dji_log_parser::Error>(())
+//! ```
+//!
+//! ### Writing logs
+//!
+//! Records can be written back into a log file with a `DJILogWriter`. Details are written with
+//! the layout of the target version, and records are Xor encoded starting with version 7.
+//! Logs version 13 and above are written without AES encryption, with `DJILogWriter::new_with_auxiliary`:
+//!
+//! ```no_run
+//! # use std::fs::File;
+//! # use dji_log_parser::DJILogWriter;
+//! # let path = "path/to/output.txt";
+//! # use dji_log_parser::DJILog;
+//! # let parser = DJILog::from_bytes(std::fs::read("path/to/log.txt")?)?;
+//! let mut writer = DJILogWriter::new(File::create(path)?, parser.version, parser.details.clone())?;
+//! for record in parser.records(None)? {
+//!     writer.write_record(&record)?;
+//! }
+//! writer.finish()?;
+//! # Ok::<(), dji_log_parser::Error>(())
+//! ```
+//!
+//! ### Decrypting logs
+//!
+//! Logs version 13 and above can be decrypted once keychains are fetched. Records are copied
+//! byte for byte once decrypted, and only Xor encoded. The copy is a version 12 log, which can
+//! be read without keychains, unless a record is too large for version 12. The copy then keeps
+//! the version of the log, and is read with an empty keychain list:
+//!
+//! ```no_run
+//! # use dji_log_parser::DJILog;
+//! # let parser = DJILog::from_bytes(std::fs::read("path/to/log.txt")?)?;
+//! # let keychains = parser.fetch_keychains("__DJI_API_KEY__")?;
+//! let bytes = parser.decrypt(Some(keychains))?;
+//!
+//! let copy = DJILog::from_bytes(bytes)?;
+//! let keychains = if copy.version >= 13 { Some(Vec::new()) } else { None };
+//! let records = copy.records(keychains)?;
+//! # Ok::<(), dji_log_parser::Error>(())
+//! ```
+//!
+//! ### Verifying integrity
+//!
+//! The layout, the record framing and the records of a log can be checked for alteration or
+//! corruption. Each check passes, fails, or is unknown when it cannot be performed, like decrypting
+//! records without keychains. Checks the parser cannot compute, like the Details checksum whose
+//! algorithm is not known, are unsupported and left out of the overall status:
+//!
+//! ```no_run
+//! # use dji_log_parser::DJILog;
+//! # let parser = DJILog::from_bytes(std::fs::read("path/to/log.txt")?)?;
+//! # let keychains = parser.fetch_keychains("__DJI_API_KEY__")?;
+//! let report = parser.verify_integrity(Some(keychains))?;
+//! println!("{:?}", report.status);
+//! # Ok::<(), dji_log_parser::Error>(())
+//! ```
+//!
+//! ### Fault summary
+//!
+//! Motor, IMU, compass and barometer faults reported by OSD records can be summarized for the
+//! whole flight to pre-screen hardware faults:
+//!
+//! ```no_run
+//! # use dji_log_parser::DJILog;
+//! # let parser = DJILog::from_bytes(std::fs::read("path/to/log.txt")?)?;
+//! # let keychains = parser.fetch_keychains("__DJI_API_KEY__")?;
+//! let summary = parser.fault_summary(Some(keychains))?;
+//! println!("{}", summary.has_fault());
+//! # Ok::<(), dji_log_parser::Error>(())
+//! ```
+//!
+//! ### Anonymizing logs
+//!
+//! Serial numbers and addresses can be blanked, and coordinates moved with a secret transform,
+//! before sharing a log. For versions 1 to 12, the anonymized log is re-encoded and can still
+//! be parsed:
+//!
+//! ```no_run
+//! # use dji_log_parser::anonymize::{Anonymizer, CoordinateTransform};
+//! # use dji_log_parser::DJILog;
+//! # let parser = DJILog::from_bytes(std::fs::read("path/to/log.txt")?)?;
+//! let anonymizer = Anonymizer::new(CoordinateTransform::from_secret(b"__SECRET__"));
+//! let bytes = parser.anonymize(&anonymizer)?;
+//! # Ok::<(), dji_log_parser::Error>(())
+//! ```
+//!
+//! Logs version 13 and above cannot be re-encrypted, and are anonymized to JSON instead:
+//!
+//! ```no_run
+//! # use dji_log_parser::anonymize::{Anonymizer, CoordinateTransform};
+//! # use dji_log_parser::DJILog;
+//! # let parser = DJILog::from_bytes(std::fs::read("path/to/log.txt")?)?;
+//! # let keychains = parser.fetch_keychains("__DJI_API_KEY__")?;
+//! # let anonymizer = Anonymizer::new(CoordinateTransform::from_secret(b"__SECRET__"));
+//! let json = parser.anonymize_json(Some(keychains), &anonymizer)?;
+//! # Ok::<(), dji_log_parser::Error>(())
 //! ```
 //!
 //!
@@ -142,30 +379,69 @@ use base64::engine::general_purpose::STANDARD as Base64Standard;
 use base64::Engine as _;
 use binrw::io::Cursor;
 use binrw::BinRead;
-use std::cell::RefCell;
-use std::collections::VecDeque;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::sync::Mutex;
 
+pub mod anonymize;
 mod decoder;
 mod error;
+mod fault_summary;
 pub mod frame;
+mod integrity;
 pub mod keychain;
 pub mod layout;
 pub mod record;
-mod utils;
+mod record_iter;
+mod report;
+mod summary;
+#[doc(hidden)]
+pub mod utils;
+mod writer;
 
+use anonymize::Anonymizer;
 pub use error::{Error, Result};
+pub use fault_summary::FaultSummary;
 use frame::{records_to_frames, Frame};
-use keychain::{EncodedKeychainFeaturePoint, Keychain, KeychainFeaturePoint, KeychainsRequest};
+use integrity::check_framing;
+pub use integrity::{IntegrityCheck, IntegrityCheckKind, IntegrityReport, IntegrityStatus};
+#[cfg(not(target_arch = "wasm32"))]
+use keychain::KeychainClient;
+use keychain::{
+    EncodedKeychainFeaturePoint, FeaturePoint, Keychain, KeychainFeaturePoint, KeychainStatus,
+    KeychainStore, KeychainsRequest, MemoryKeychainStore,
+};
 use layout::auxiliary::{Auxiliary, Department};
 use layout::details::Details;
 use layout::prefix::Prefix;
 use record::Record;
+pub use record_iter::{RawRecord, RawRecordIter, RecordIter};
+pub use report::{DecryptionStats, ParseIssue, ParseReport, RecoveryAction, RecoveryMode};
+pub use summary::LogSummary;
+pub use writer::DJILogWriter;
 
+use crate::decoder::{lock_reader, BufferedReader, SeekRead, SharedReader};
 use crate::utils::pad_with_zeros;
 
-#[derive(Debug)]
+/// Number of records decrypted per feature point by `DJILog::validate_keychains`
+const KEYCHAIN_VALIDATION_RECORDS: usize = 3;
+
+/// Keychains of each log of a batch, with the indexes and requests of the logs still to fetch
+type BatchRequests = (
+    Vec<Result<Vec<Vec<KeychainFeaturePoint>>>>,
+    Vec<usize>,
+    Vec<KeychainsRequest>,
+);
+
+/// A DJI log file.
+///
+/// `DJILog` is `Send` and `Sync`. Records are decoded from a single reader, so record
+/// iterators used from several threads decode one record at a time.
+///
 pub struct DJILog {
-    inner: Vec<u8>,
+    inner: SharedReader,
+    size: u64,
     prefix: Prefix,
     /// Log format version
     pub version: u8,
@@ -173,6 +449,17 @@ pub struct DJILog {
     pub details: Details,
 }
 
+impl fmt::Debug for DJILog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DJILog")
+            .field("size", &self.size)
+            .field("prefix", &self.prefix)
+            .field("version", &self.version)
+            .field("details", &self.details)
+            .finish_non_exhaustive()
+    }
+}
+
 impl DJILog {
     /// Constructs a `DJILog` from an array of bytes.
     ///
@@ -190,49 +477,222 @@ impl DJILog {
     ///
     /// # Examples
     ///
-    /// ```
-    /// use djilog_parser::DJILog;
+    /// ```no_run
+    /// use dji_log_parser::DJILog;
     ///
-    /// let log_bytes = include_bytes!("path/to/log/file");
+    /// let log_bytes = std::fs::read("path/to/log/file").unwrap();
     /// let log = DJILog::from_bytes(log_bytes).unwrap();
     /// ```
     ///
     pub fn from_bytes(bytes: Vec<u8>) -> Result<DJILog> {
+        DJILog::from_source(Box::new(Cursor::new(bytes)))
+    }
+
+    /// Constructs a `DJILog` from a reader.
+    ///
+    /// Only the Prefix and Info blocks are read when the `DJILog` is constructed. Records are
+    /// decoded from the reader on demand, so the log file never has to be fully loaded in memory.
+    /// The reader is internally buffered, and the buffer is kept when seeking within it, so the
+    /// records section is read from the reader about once.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - A reader implementing `Read` and `Seek`, positioned anywhere in the DJI log file.
+    ///
+    /// # Returns
+    ///
+    /// This function returns `Result<DJILog>`.
+    /// On success, it returns the `DJILog` instance.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dji_log_parser::DJILog;
+    ///
+    /// let file = std::fs::File::open("path/to/log/file").unwrap();
+    /// let log = DJILog::from_reader(file).unwrap();
+    /// ```
+    ///
+    pub fn from_reader<R: Read + Seek + Send + 'static>(reader: R) -> Result<DJILog> {
+        DJILog::from_source(Box::new(BufferedReader::new(reader)?))
+    }
+
+    /// Constructs a `DJILog` from a memory-mapped file.
+    /// Available on native behind the `mmap` feature.
+    ///
+    /// The file is mapped in memory and pages are loaded by the operating system when records
+    /// are decoded.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - The DJI log file to map.
+    ///
+    /// # Returns
+    ///
+    /// This function returns `Result<DJILog>`.
+    /// On success, it returns the `DJILog` instance.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated while the `DJILog` is alive, as the mapped
+    /// memory would change underneath the parser. See `memmap2::Mmap::map` for details.
+    ///
+    #[cfg(all(not(target_arch = "wasm32"), feature = "mmap"))]
+    pub unsafe fn from_mmap(file: &std::fs::File) -> Result<DJILog> {
+        let mmap = memmap2::Mmap::map(file)?;
+        DJILog::from_source(Box::new(Cursor::new(mmap)))
+    }
+
+    fn from_source(mut reader: Box<dyn SeekRead + Send>) -> Result<DJILog> {
+        let size = reader.seek(SeekFrom::End(0))?;
+        reader.seek(SeekFrom::Start(0))?;
+
         // Decode Prefix
-        let mut prefix = Prefix::read(&mut Cursor::new(&bytes))?;
+        let mut prefix = Prefix::read(&mut reader)?;
 
         let version = prefix.version;
 
-        // Decode Detail
-        let detail_offset = prefix.detail_offset() as usize;
-        let mut cursor = Cursor::new(pad_with_zeros(&bytes[detail_offset..], 400));
-
-        let details = if version < 13 {
-            Details::read_args(&mut cursor, (version,))?
-        } else {
-            // Get details from first auxiliary block
-            if let Auxiliary::Info(data) = Auxiliary::read(&mut cursor)? {
-                Details::read_args(&mut Cursor::new(&data.info_data), (version,))?
-            } else {
-                return Err(Error::MissingAuxilliaryData("Info".into()));
-            }
-        };
+        let details = DJILog::read_details(&mut reader, &prefix)?;
 
         // Try to recover detail offset
         if prefix.records_offset() == 0 && version >= 13 {
             // Skip second auxiliary block
-            let _ = Auxiliary::read(&mut cursor)?;
-            prefix.recover_detail_offset(cursor.position() + detail_offset as u64);
+            let _ = Auxiliary::read(&mut reader)?;
+            prefix.recover_detail_offset(reader.stream_position()?);
         }
 
         Ok(DJILog {
-            inner: bytes,
+            inner: Mutex::new(reader),
+            size,
             prefix,
             version,
             details,
         })
     }
 
+    /// Reads the metadata of a DJI log without decoding any record.
+    ///
+    /// Only the Prefix and the Details are read, or the first two Auxiliary blocks for logs
+    /// version 13 and above. Unlike `from_reader`, the reader is not kept, so thousands of
+    /// files can be scanned quickly.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - A reader implementing `Read` and `Seek`, positioned anywhere in the DJI log file.
+    ///
+    /// # Returns
+    ///
+    /// This function returns `Result<LogSummary>`.
+    /// On success, it returns the version, details, and for logs version 13 and above,
+    /// the department and version of the Auxiliary blocks.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dji_log_parser::DJILog;
+    ///
+    /// let file = std::fs::File::open("path/to/log/file").unwrap();
+    /// let summary = DJILog::probe(file).unwrap();
+    /// ```
+    ///
+    pub fn probe<R: Read + Seek>(reader: R) -> Result<LogSummary> {
+        let mut reader = BufferedReader::new(reader)?;
+        reader.seek(SeekFrom::Start(0))?;
+
+        let prefix = Prefix::read(&mut reader)?;
+        let details = DJILog::read_details(&mut reader, &prefix)?;
+
+        // Get department and version from second auxiliary block
+        let (department, auxiliary_version) = if prefix.version >= 13 {
+            if let Auxiliary::Version(data) = Auxiliary::read(&mut reader)? {
+                (Some(data.department), Some(data.version))
+            } else {
+                return Err(Error::MissingAuxilliaryData("Version".into()));
+            }
+        } else {
+            (None, None)
+        };
+
+        Ok(LogSummary {
+            version: prefix.version,
+            product_type: details.product_type,
+            start_time: details.start_time,
+            aircraft_sn: details.aircraft_sn.clone(),
+            department,
+            auxiliary_version,
+            details,
+        })
+    }
+
+    /// Returns the Prefix block of the log.
+    ///
+    /// For logs version 13 and above with a missing records offset, the offset recovered
+    /// after the Auxiliary blocks is returned.
+    ///
+    pub fn prefix(&self) -> &Prefix {
+        &self.prefix
+    }
+
+    /// Reads the Auxiliary blocks of the log.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<Vec<Auxiliary>>` with the Info and Version blocks, in file order.
+    /// Logs prior to version 13 have no Auxiliary block, and an empty vector is returned.
+    ///
+    pub fn auxiliary(&self) -> Result<Vec<Auxiliary>> {
+        if self.version < 13 {
+            return Ok(Vec::new());
+        }
+
+        let mut reader = lock_reader(&self.inner);
+        reader.seek(SeekFrom::Start(self.prefix.detail_offset()))?;
+
+        let info = Auxiliary::read(&mut *reader)?;
+        let version = Auxiliary::read(&mut *reader)?;
+
+        Ok(vec![info, version])
+    }
+
+    /// Returns the offset of the first record in the log file.
+    pub fn records_offset(&self) -> u64 {
+        self.prefix.records_offset()
+    }
+
+    /// Returns the offset of the end of the records section in the log file.
+    pub fn records_end_offset(&self) -> u64 {
+        self.prefix.records_end_offset(self.size)
+    }
+
+    /// Reads the Details block, or the first Auxiliary block for logs version 13 and above.
+    /// The reader is left at the end of the block.
+    fn read_details<R: Read + Seek>(reader: &mut R, prefix: &Prefix) -> Result<Details> {
+        let version = prefix.version;
+
+        // Decode Detail
+        let detail_offset = prefix.detail_offset();
+        reader.seek(SeekFrom::Start(detail_offset))?;
+
+        if version < 13 {
+            let mut buffer = Vec::new();
+            reader.take(400).read_to_end(&mut buffer)?;
+            Ok(Details::read_args(
+                &mut Cursor::new(pad_with_zeros(&buffer, 400)),
+                (version,),
+            )?)
+        } else {
+            // Get details from first auxiliary block
+            if let Auxiliary::Info(data) = Auxiliary::read(reader)? {
+                Ok(Details::read_args(
+                    &mut Cursor::new(&data.info_data),
+                    (version,),
+                )?)
+            } else {
+                Err(Error::MissingAuxilliaryData("Info".into()))
+            }
+        }
+    }
+
     /// Creates a `KeychainsRequest` object by parsing `KeyStorage` records.
     ///
     /// This function is used to build a request body for manually retrieving the keychain from the DJI API.
@@ -271,21 +731,47 @@ impl DJILog {
         department: Option<Department>,
         version: Option<u16>,
     ) -> Result<KeychainsRequest> {
+        Ok(self.keychains_request_with_report(department, version)?.0)
+    }
+
+    /// Creates a `KeychainsRequest` object by parsing `KeyStorage` records, along with parse diagnostics.
+    ///
+    /// This function builds the request like `keychains_request_with_custom_params`, and also returns
+    /// the `ParseReport` of the records decoding. Records are decoded up to the first failure, so a
+    /// report that is not clean means the request may miss some keychains.
+    ///
+    /// # Arguments
+    ///
+    /// * `department` - An optional `Department` to manually set in the request. If `None`, the department
+    ///   will be determined from the log file.
+    /// * `version` - An optional version number to manually set in the request. If `None`, the version
+    ///   will be determined from the log file.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<(KeychainsRequest, ParseReport)>`. On success, it provides the `KeychainsRequest`
+    /// and the `ParseReport` of the decoding. For logs prior to version 13, the report is empty.
+    ///
+    pub fn keychains_request_with_report(
+        &self,
+        department: Option<Department>,
+        version: Option<u16>,
+    ) -> Result<(KeychainsRequest, ParseReport)> {
         let mut keychain_request = KeychainsRequest::default();
 
         // No keychain
         if self.version < 13 {
-            return Ok(keychain_request);
+            return Ok((keychain_request, ParseReport::default()));
         }
 
-        let mut cursor = Cursor::new(&self.inner);
-        cursor.set_position(self.prefix.detail_offset());
+        let mut reader = lock_reader(&self.inner);
+        reader.seek(SeekFrom::Start(self.prefix.detail_offset()))?;
 
         // Skip first auxiliary block
-        let _ = Auxiliary::read(&mut cursor)?;
+        let _ = Auxiliary::read(&mut *reader)?;
 
         // Get version from second auxilliary block
-        if let Auxiliary::Version(data) = Auxiliary::read(&mut cursor)? {
+        if let Auxiliary::Version(data) = Auxiliary::read(&mut *reader)? {
             // Use provided version or determine from log
             keychain_request.version = version.unwrap_or(data.version);
             // Use provided department or determine from log
@@ -299,25 +785,20 @@ impl DJILog {
         } else {
             return Err(Error::MissingAuxilliaryData("Version".into()));
         }
+        drop(reader);
 
         // Extract keychains from KeyStorage Records
-        cursor.set_position(self.prefix.records_offset());
+        let mut records = RecordIter::new(
+            &self.inner,
+            self.prefix.records_offset(),
+            self.prefix.records_end_offset(self.size),
+            self.version,
+            VecDeque::new(),
+        );
 
         let mut keychain: Vec<EncodedKeychainFeaturePoint> = Vec::new();
 
-        while cursor.position() < self.prefix.records_end_offset(self.inner.len() as u64) {
-            let empty_keychain = &RefCell::new(Keychain::empty());
-            let record = match Record::read_args(
-                &mut cursor,
-                binrw::args! {
-                    version: self.version,
-                    keychain: empty_keychain
-                },
-            ) {
-                Ok(record) => record,
-                Err(_) => break,
-            };
-
+        for record in records.by_ref().map_while(Result::ok) {
             match record {
                 Record::KeyStorage(data) => {
                     // add EncodedKeychainFeaturePoint to current keychain
@@ -337,13 +818,14 @@ impl DJILog {
 
         keychain_request.keychains.push(keychain);
 
-        Ok(keychain_request)
+        Ok((keychain_request, records.into_report()))
     }
 
     /// Fetches keychains using the provided API key.
     ///
     /// This function first creates a `KeychainRequest` using the `keychain_request()` method,
-    /// then uses that request to fetch the actual keychains from the DJI API.
+    /// then uses that request to fetch the actual keychains from the DJI API through a
+    /// `KeychainClient` with its default retry policy.
     /// Keychains are required to decode records for logs with a version greater than or equal to 13.
     ///
     /// # Arguments
@@ -358,7 +840,7 @@ impl DJILog {
     #[cfg(not(target_arch = "wasm32"))]
     pub fn fetch_keychains(&self, api_key: &str) -> Result<Vec<Vec<KeychainFeaturePoint>>> {
         if self.version >= 13 {
-            self.keychains_request()?.fetch(api_key, None)
+            KeychainClient::new(api_key).fetch(&self.keychains_request()?)
         } else {
             Ok(Vec::new())
         }
@@ -369,6 +851,7 @@ impl DJILog {
     ///
     /// This function first creates a `KeychainRequest` using the `keychain_request()` method,
     /// then uses that request to asynchronously fetch the actual keychains from the DJI API.
+    /// On native targets, the request goes through a `KeychainClient` with its default retry policy.
     /// Keychains are required to decode records for logs with a version greater than or equal to 13.
     ///
     /// # Arguments
@@ -386,15 +869,266 @@ impl DJILog {
         api_key: &str,
     ) -> Result<Vec<Vec<KeychainFeaturePoint>>> {
         if self.version >= 13 {
-            self.keychains_request()?.fetch_async(api_key, None).await
+            fetch_async(api_key, &self.keychains_request()?).await
         } else {
             Ok(Vec::new())
         }
     }
 
-    /// Retrieves the parsed raw records from the DJI log.
+    /// Fetches keychains using the provided API key, going through a `KeychainStore`.
     ///
-    /// This function decodes the raw records from the log file
+    /// The store is consulted first with the fingerprint of the log `KeychainsRequest`. The DJI API
+    /// is only called when no entry is found, and the retrieved keychains are then written back
+    /// to the store.
+    ///
+    /// # Arguments
+    ///
+    /// * `api_key` - A string slice that holds the API key for authentication with the DJI API.
+    /// * `store` - The `KeychainStore` used to cache keychains.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<Vec<Vec<KeychainFeaturePoint>>>`. On success, it provides a vector of vectors,
+    /// where each inner vector represents a keychain.
+    ///
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn fetch_keychains_with_store(
+        &self,
+        api_key: &str,
+        store: &mut dyn KeychainStore,
+    ) -> Result<Vec<Vec<KeychainFeaturePoint>>> {
+        if self.version < 13 {
+            return Ok(Vec::new());
+        }
+
+        let request = self.keychains_request()?;
+        let fingerprint = request.fingerprint();
+
+        if let Some(keychains) = store.get(&fingerprint)? {
+            return Ok(keychains);
+        }
+
+        let keychains = KeychainClient::new(api_key).fetch(&request)?;
+        store.put(&fingerprint, &keychains)?;
+
+        Ok(keychains)
+    }
+
+    /// Fetches keychains asynchronously using the provided API key, going through a `KeychainStore`.
+    /// Available on wasm and native behind the `native-async` feature.
+    ///
+    /// See `fetch_keychains_with_store` for details.
+    ///
+    /// # Arguments
+    ///
+    /// * `api_key` - A string slice that holds the API key for authentication with the DJI API.
+    /// * `store` - The `KeychainStore` used to cache keychains.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<Vec<Vec<KeychainFeaturePoint>>>`. On success, it provides a vector of vectors,
+    /// where each inner vector represents a keychain.
+    ///
+    #[cfg(any(target_arch = "wasm32", feature = "native-async"))]
+    pub async fn fetch_keychains_with_store_async(
+        &self,
+        api_key: &str,
+        store: &mut dyn KeychainStore,
+    ) -> Result<Vec<Vec<KeychainFeaturePoint>>> {
+        if self.version < 13 {
+            return Ok(Vec::new());
+        }
+
+        let request = self.keychains_request()?;
+        let fingerprint = request.fingerprint();
+
+        if let Some(keychains) = store.get(&fingerprint)? {
+            return Ok(keychains);
+        }
+
+        let keychains = fetch_async(api_key, &request).await?;
+        store.put(&fingerprint, &keychains)?;
+
+        Ok(keychains)
+    }
+
+    /// Fetches keychains of several logs using the provided API key, with as few API calls as possible.
+    ///
+    /// The keychain requests of logs sharing a version and a department are merged into a single
+    /// call, and the response is split back per log. Calls go through a `KeychainClient` with its
+    /// default retry policy, and a failed call only fails the logs it holds keychains of.
+    ///
+    /// # Arguments
+    ///
+    /// * `logs` - The logs to fetch keychains for.
+    /// * `api_key` - A string slice that holds the API key for authentication with the DJI API.
+    /// * `max_batch_size` - The maximum number of keychains sent in a single API call.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Vec<Result<Vec<Vec<KeychainFeaturePoint>>>>` with the keychains of each log, in the
+    /// order of `logs`. Logs with a version lower than 13 get empty keychains.
+    ///
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn fetch_keychains_batch(
+        logs: &[&DJILog],
+        api_key: &str,
+        max_batch_size: usize,
+    ) -> Vec<Result<Vec<Vec<KeychainFeaturePoint>>>> {
+        Self::fetch_keychains_batch_with_store(
+            logs,
+            api_key,
+            &mut MemoryKeychainStore::new(),
+            max_batch_size,
+        )
+    }
+
+    /// Fetches keychains of several logs using the provided API key, going through a `KeychainStore`.
+    ///
+    /// Logs whose keychains are found in the store are not sent to the DJI API. The keychains of
+    /// the other logs are fetched like `fetch_keychains_batch`, and written back to the store.
+    ///
+    /// # Arguments
+    ///
+    /// * `logs` - The logs to fetch keychains for.
+    /// * `api_key` - A string slice that holds the API key for authentication with the DJI API.
+    /// * `store` - The `KeychainStore` used to cache keychains.
+    /// * `max_batch_size` - The maximum number of keychains sent in a single API call.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Vec<Result<Vec<Vec<KeychainFeaturePoint>>>>` with the keychains of each log, in the
+    /// order of `logs`. Logs with a version lower than 13 get empty keychains.
+    ///
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn fetch_keychains_batch_with_store(
+        logs: &[&DJILog],
+        api_key: &str,
+        store: &mut dyn KeychainStore,
+        max_batch_size: usize,
+    ) -> Vec<Result<Vec<Vec<KeychainFeaturePoint>>>> {
+        let (mut results, indexes, requests) = Self::batch_requests(logs, store);
+        let fetched = KeychainClient::new(api_key).fetch_batch(&requests, max_batch_size);
+        Self::store_batch_results(&mut results, &indexes, &requests, fetched, store);
+
+        results
+    }
+
+    /// Fetches keychains of several logs asynchronously using the provided API key, with as few
+    /// API calls as possible.
+    /// Available on wasm and native behind the `native-async` feature.
+    ///
+    /// See `fetch_keychains_batch` for details.
+    ///
+    /// # Arguments
+    ///
+    /// * `logs` - The logs to fetch keychains for.
+    /// * `api_key` - A string slice that holds the API key for authentication with the DJI API.
+    /// * `max_batch_size` - The maximum number of keychains sent in a single API call.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Vec<Result<Vec<Vec<KeychainFeaturePoint>>>>` with the keychains of each log, in the
+    /// order of `logs`. Logs with a version lower than 13 get empty keychains.
+    ///
+    #[cfg(any(target_arch = "wasm32", feature = "native-async"))]
+    pub async fn fetch_keychains_batch_async(
+        logs: &[&DJILog],
+        api_key: &str,
+        max_batch_size: usize,
+    ) -> Vec<Result<Vec<Vec<KeychainFeaturePoint>>>> {
+        Self::fetch_keychains_batch_with_store_async(
+            logs,
+            api_key,
+            &mut MemoryKeychainStore::new(),
+            max_batch_size,
+        )
+        .await
+    }
+
+    /// Fetches keychains of several logs asynchronously using the provided API key, going through
+    /// a `KeychainStore`.
+    /// Available on wasm and native behind the `native-async` feature.
+    ///
+    /// See `fetch_keychains_batch_with_store` for details.
+    ///
+    /// # Arguments
+    ///
+    /// * `logs` - The logs to fetch keychains for.
+    /// * `api_key` - A string slice that holds the API key for authentication with the DJI API.
+    /// * `store` - The `KeychainStore` used to cache keychains.
+    /// * `max_batch_size` - The maximum number of keychains sent in a single API call.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Vec<Result<Vec<Vec<KeychainFeaturePoint>>>>` with the keychains of each log, in the
+    /// order of `logs`. Logs with a version lower than 13 get empty keychains.
+    ///
+    #[cfg(any(target_arch = "wasm32", feature = "native-async"))]
+    pub async fn fetch_keychains_batch_with_store_async(
+        logs: &[&DJILog],
+        api_key: &str,
+        store: &mut dyn KeychainStore,
+        max_batch_size: usize,
+    ) -> Vec<Result<Vec<Vec<KeychainFeaturePoint>>>> {
+        let (mut results, indexes, requests) = Self::batch_requests(logs, store);
+        let fetched =
+            KeychainsRequest::fetch_batch_async(&requests, api_key, None, max_batch_size).await;
+        Self::store_batch_results(&mut results, &indexes, &requests, fetched, store);
+
+        results
+    }
+
+    /// Builds the keychain requests of several logs, and looks their keychains up in the store.
+    ///
+    /// Returns the result of each log, and the indexes and requests of the logs whose keychains
+    /// are still to be fetched. The results of these logs are empty keychains until fetched.
+    fn batch_requests(logs: &[&DJILog], store: &dyn KeychainStore) -> BatchRequests {
+        let mut results = Vec::with_capacity(logs.len());
+        let mut indexes = Vec::new();
+        let mut requests = Vec::new();
+
+        for (index, log) in logs.iter().enumerate() {
+            if log.version < 13 {
+                results.push(Ok(Vec::new()));
+                continue;
+            }
+
+            let result = log.keychains_request().and_then(|request| {
+                let keychains = store.get(&request.fingerprint())?;
+                if keychains.is_none() {
+                    indexes.push(index);
+                    requests.push(request);
+                }
+                Ok(keychains.unwrap_or_default())
+            });
+            results.push(result);
+        }
+
+        (results, indexes, requests)
+    }
+
+    /// Writes the fetched keychains of logs to the store, and to the results of the logs.
+    fn store_batch_results(
+        results: &mut [Result<Vec<Vec<KeychainFeaturePoint>>>],
+        indexes: &[usize],
+        requests: &[KeychainsRequest],
+        fetched: Vec<Result<Vec<Vec<KeychainFeaturePoint>>>>,
+        store: &mut dyn KeychainStore,
+    ) {
+        for ((index, request), result) in indexes.iter().zip(requests).zip(fetched) {
+            results[*index] = result.and_then(|keychains| {
+                store.put(&request.fingerprint(), &keychains)?;
+                Ok(keychains)
+            });
+        }
+    }
+
+    /// Returns a lazy iterator over the parsed raw records of the DJI log.
+    ///
+    /// Unlike `records`, this function does not materialize the whole records section in memory:
+    /// each record is decoded when the iterator is advanced. The keychain rollover on
+    /// `KeyStorageRecover` records is handled internally.
     ///
     /// # Arguments
     ///
@@ -405,53 +1139,340 @@ impl DJILog {
     ///
     /// # Returns
     ///
-    /// Returns a `Result<Vec<Record>>`. On success, it provides a vector of `Record`
-    /// instances representing the parsed log records.
+    /// Returns a `Result<RecordIter>`. On success, it provides an iterator yielding `Result<Record>` items.
+    /// The iterator yields an error once if a record cannot be decoded, and then stops.
     ///
-    pub fn records(
+    pub fn record_iter(
         &self,
         keychains: Option<Vec<Vec<KeychainFeaturePoint>>>,
-    ) -> Result<Vec<Record>> {
+    ) -> Result<RecordIter<'_>> {
         if self.version >= 13 && keychains.is_none() {
             return Err(Error::KeychainRequired);
         }
 
-        let mut keychains = VecDeque::from(match keychains {
+        let keychains = match keychains {
             Some(keychains) => keychains
                 .iter()
                 .map(Keychain::from_feature_points)
                 .collect(),
-            None => Vec::new(),
-        });
+            None => VecDeque::new(),
+        };
+
+        Ok(RecordIter::new(
+            &self.inner,
+            self.prefix.records_offset(),
+            self.prefix.records_end_offset(self.size),
+            self.version,
+            keychains,
+        ))
+    }
+
+    /// Returns a lazy iterator over the records of the DJI log, with their offset and decoded bytes.
+    ///
+    /// Each `RawRecord` keeps the offset, type and declared length of the record, and its content
+    /// once Xor decoded and AES decrypted, along with the parsed `Record`. This is meant to compare
+    /// record layouts across firmware versions.
+    ///
+    /// # Arguments
+    ///
+    /// * `keychains` - An optional vector of vectors containing `KeychainFeaturePoint` instances. This parameter
+    ///   is used for decryption when working with encrypted logs (versions >= 13). If `None` is provided,
+    ///   the function will attempt to process the log without decryption.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<RawRecordIter>`. On success, it provides an iterator yielding `Result<RawRecord>` items.
+    /// The iterator yields an error once if a record cannot be decoded, and then stops.
+    ///
+    pub fn raw_record_iter(
+        &self,
+        keychains: Option<Vec<Vec<KeychainFeaturePoint>>>,
+    ) -> Result<RawRecordIter<'_>> {
+        Ok(RawRecordIter::new(self.record_iter(keychains)?))
+    }
 
-        let mut cursor = Cursor::new(&self.inner);
-        cursor.set_position(self.prefix.records_offset());
+    /// Checks keychains against the first encrypted records of each feature point.
+    ///
+    /// Records are decoded until `KEYCHAIN_VALIDATION_RECORDS` records of each feature point of
+    /// the keychains were decrypted, or until the end of the log. A record failing the AES
+    /// padding check means the key or IV of its feature point is wrong, or belongs to another log.
+    ///
+    /// # Arguments
+    ///
+    /// * `keychains` - The keychains to validate, as returned by `fetch_keychains`.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<HashMap<FeaturePoint, KeychainStatus>>` with the status of each feature
+    /// point of the keychains. Logs prior to version 13 are not encrypted, and all feature points
+    /// are `Untested`.
+    ///
+    pub fn validate_keychains(
+        &self,
+        keychains: Vec<Vec<KeychainFeaturePoint>>,
+    ) -> Result<HashMap<FeaturePoint, KeychainStatus>> {
+        let feature_points: HashSet<FeaturePoint> = keychains
+            .iter()
+            .flatten()
+            .map(|entry| entry.feature_point)
+            .collect();
 
-        let mut keychain = RefCell::new(keychains.pop_front().unwrap_or(Keychain::empty()));
+        let is_sampled = |report: &ParseReport| {
+            feature_points.iter().all(|feature_point| {
+                report.decryption.get(feature_point).is_some_and(|stats| {
+                    stats.decrypted + stats.failed >= KEYCHAIN_VALIDATION_RECORDS
+                })
+            })
+        };
 
-        let mut records = Vec::new();
+        let mut records = self
+            .record_iter(Some(keychains))?
+            .with_recovery(RecoveryMode::Resync);
+        while !is_sampled(records.report()) && records.next().is_some() {}
+        let report = records.into_report();
 
-        while cursor.position() < self.prefix.records_end_offset(self.inner.len() as u64) {
-            // decode record
-            let record = match Record::read_args(
-                &mut cursor,
-                binrw::args! {
-                    version: self.version,
-                    keychain: &keychain
-                },
-            ) {
-                Ok(record) => record,
-                Err(_) => break,
-            };
+        Ok(feature_points
+            .into_iter()
+            .map(|feature_point| {
+                let status = match report.decryption.get(&feature_point) {
+                    Some(stats) if stats.failed > 0 => KeychainStatus::Invalid,
+                    Some(stats) if stats.decrypted > 0 => KeychainStatus::Valid,
+                    _ => KeychainStatus::Untested,
+                };
+                (feature_point, status)
+            })
+            .collect())
+    }
+
+    /// Checks that the log was not altered or corrupted after the flight.
+    ///
+    /// - `Layout`: the Details block is complete, or the Auxiliary blocks for logs version 13
+    ///   and above, and the offsets of the prefix match the blocks and the records section.
+    /// - `Framing`: records are framed end to end, from the first record to the end of the
+    ///   records section. This check does not decode records, and does not require keychains.
+    /// - `Records`: all records are decoded, and decrypted for logs version 13 and above.
+    /// - `DetailsChecksum`: the algorithm of `detail_info_checksum` is not known, and the check
+    ///   is always `Unknown`.
+    /// - `Signature`: logs version 13 and above without signature fail. The signature cannot be
+    ///   verified without DJI's key, and the check is `Unknown` otherwise.
+    ///
+    /// As the authenticity of the log cannot be verified, the overall status is never `Pass`:
+    /// it is `Fail` when a check failed, and `Unknown` otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `keychains` - An optional vector of vectors containing `KeychainFeaturePoint` instances. This parameter
+    ///   is used for decryption when working with encrypted logs (versions >= 13). If `None` is provided,
+    ///   the `Records` check of encrypted logs is `Unknown`.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<IntegrityReport>` with the status of each check.
+    ///
+    pub fn verify_integrity(
+        &self,
+        keychains: Option<Vec<Vec<KeychainFeaturePoint>>>,
+    ) -> Result<IntegrityReport> {
+        let auxiliary = if self.version >= 13 {
+            self.auxiliary()
+        } else {
+            Ok(Vec::new())
+        };
 
-            if let Record::KeyStorageRecover(_) = record {
-                keychain = RefCell::new(keychains.pop_front().unwrap_or(Keychain::empty()));
+        let layout = match &auxiliary {
+            Err(e) => Err(format!("Auxiliary blocks are corrupted: {}", e)),
+            Ok(_) => self.check_layout(),
+        };
+        let layout = match layout {
+            Ok(message) => {
+                IntegrityCheck::new(IntegrityCheckKind::Layout, IntegrityStatus::Pass, message)
             }
+            Err(message) => {
+                IntegrityCheck::new(IntegrityCheckKind::Layout, IntegrityStatus::Fail, message)
+            }
+        };
+
+        let framing = check_framing(
+            &mut **lock_reader(&self.inner),
+            self.records_offset(),
+            self.records_end_offset(),
+            self.version,
+        );
+        let framing = match framing {
+            Ok(count) => IntegrityCheck::new(
+                IntegrityCheckKind::Framing,
+                IntegrityStatus::Pass,
+                format!("{} records framed", count),
+            ),
+            Err(message) => {
+                IntegrityCheck::new(IntegrityCheckKind::Framing, IntegrityStatus::Fail, message)
+            }
+        };
+
+        let records = if self.version >= 13 && keychains.is_none() {
+            IntegrityCheck::new(
+                IntegrityCheckKind::Records,
+                IntegrityStatus::Unknown,
+                "Keychains are required to decrypt records",
+            )
+        } else {
+            let mut records = self
+                .record_iter(keychains)?
+                .with_recovery(RecoveryMode::Resync);
+            let count = records.by_ref().filter(Result::is_ok).count();
+            let report = records.into_report();
+
+            if report.is_clean() && report.decryption_failures() == 0 {
+                IntegrityCheck::new(
+                    IntegrityCheckKind::Records,
+                    IntegrityStatus::Pass,
+                    format!("{} records decoded", count),
+                )
+            } else {
+                IntegrityCheck::new(
+                    IntegrityCheckKind::Records,
+                    IntegrityStatus::Fail,
+                    format!(
+                        "{} issues while decoding records, {} records failed to decrypt",
+                        report.issues.len(),
+                        report.decryption_failures()
+                    ),
+                )
+            }
+        };
+
+        let details_checksum = IntegrityCheck::new(
+            IntegrityCheckKind::DetailsChecksum,
+            IntegrityStatus::Unknown,
+            "Checksum algorithm of the Details block is not known",
+        );
+
+        let signature_data = auxiliary.ok().and_then(|blocks| {
+            blocks.into_iter().find_map(|block| match block {
+                Auxiliary::Info(data) => Some(data.signature_data),
+                _ => None,
+            })
+        });
+        let (status, message) = match signature_data {
+            None if self.version < 13 => (
+                IntegrityStatus::Unknown,
+                "Logs prior to version 13 are not signed",
+            ),
+            Some(data) if !data.is_empty() => (
+                IntegrityStatus::Unknown,
+                "Signature cannot be verified without DJI's key",
+            ),
+            _ => (IntegrityStatus::Fail, "Signature is missing"),
+        };
+        let signature = IntegrityCheck::new(IntegrityCheckKind::Signature, status, message);
+
+        Ok(IntegrityReport::new(vec![
+            layout,
+            framing,
+            records,
+            details_checksum,
+            signature,
+        ]))
+    }
 
-            records.push(record);
+    /// Checks the Details block, or the Auxiliary blocks, against the offsets of the prefix.
+    ///
+    /// Returns the reason of the failure on error.
+    fn check_layout(&self) -> std::result::Result<String, String> {
+        let records_offset = self.prefix.records_offset();
+
+        if self.version < 12 {
+            let detail_offset = self.prefix.detail_offset();
+            let detail_end = detail_offset.checked_add(self.prefix.detail_length() as u64);
+            if detail_end.is_none_or(|detail_end| detail_end > self.size) {
+                return Err(format!(
+                    "Details block is truncated at offset {}",
+                    self.size
+                ));
+            }
+            if detail_offset < records_offset {
+                return Err(format!(
+                    "Details block at offset {} is before the records section at offset {}",
+                    detail_offset, records_offset
+                ));
+            }
+            Ok("Details block is complete".to_owned())
+        } else if self.version == 12 {
+            if records_offset > self.size {
+                return Err(format!(
+                    "Details block is truncated at offset {}",
+                    self.size
+                ));
+            }
+            Ok("Details block is complete".to_owned())
+        } else {
+            let mut reader = lock_reader(&self.inner);
+            let auxiliary_end = reader
+                .seek(SeekFrom::Start(self.prefix.detail_offset()))
+                .map_err(binrw::Error::from)
+                .and_then(|_| Auxiliary::read(&mut *reader))
+                .and_then(|_| Auxiliary::read(&mut *reader))
+                .and_then(|_| Ok(reader.stream_position()?))
+                .map_err(|e| format!("Auxiliary blocks are corrupted: {}", e))?;
+
+            if auxiliary_end != records_offset {
+                return Err(format!(
+                    "Records offset {} does not match the end of the Auxiliary blocks at offset {}",
+                    records_offset, auxiliary_end
+                ));
+            }
+            Ok("Auxiliary blocks are complete".to_owned())
         }
+    }
+
+    /// Retrieves the parsed raw records from the DJI log.
+    ///
+    /// This function decodes the raw records from the log file
+    ///
+    /// # Arguments
+    ///
+    /// * `keychains` - An optional vector of vectors containing `KeychainFeaturePoint` instances. This parameter
+    ///   is used for decryption when working with encrypted logs (versions >= 13). If `None` is provided,
+    ///   the function will attempt to process the log without decryption.
+    ///
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<Vec<Record>>`. On success, it provides a vector of `Record`
+    /// instances representing the parsed log records.
+    ///
+    pub fn records(
+        &self,
+        keychains: Option<Vec<Vec<KeychainFeaturePoint>>>,
+    ) -> Result<Vec<Record>> {
+        Ok(self.record_iter(keychains)?.map_while(Result::ok).collect())
+    }
 
-        Ok(records)
+    /// Retrieves the parsed raw records from the DJI log along with parse diagnostics.
+    ///
+    /// This function decodes the raw records from the log file like `records`, and also
+    /// returns a `ParseReport` listing every decoding failure and invalid data blob encountered,
+    /// with its byte offset, record type, declared length and the action taken by the parser.
+    ///
+    /// # Arguments
+    ///
+    /// * `keychains` - An optional vector of vectors containing `KeychainFeaturePoint` instances. This parameter
+    ///   is used for decryption when working with encrypted logs (versions >= 13). If `None` is provided,
+    ///   the function will attempt to process the log without decryption.
+    ///
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<(Vec<Record>, ParseReport)>`. On success, it provides a vector of `Record`
+    /// instances representing the parsed log records, and the `ParseReport` of the decoding.
+    ///
+    pub fn records_with_report(
+        &self,
+        keychains: Option<Vec<Vec<KeychainFeaturePoint>>>,
+    ) -> Result<(Vec<Record>, ParseReport)> {
+        let mut records = self.record_iter(keychains)?;
+        let result = records.by_ref().map_while(Result::ok).collect();
+        Ok((result, records.into_report()))
     }
 
     /// Retrieves the normalized frames from the DJI log.
@@ -483,7 +1504,191 @@ impl DJILog {
     /// versions, simplifying data analysis and interpretation.
     ///
     pub fn frames(&self, keychains: Option<Vec<Vec<KeychainFeaturePoint>>>) -> Result<Vec<Frame>> {
-        let records = self.records(keychains)?;
+        let records = self.record_iter(keychains)?.map_while(Result::ok);
         Ok(records_to_frames(records, self.details.clone()))
     }
+
+    /// Summarizes the faults reported by the OSD records of the flight.
+    ///
+    /// Motor, IMU, compass and barometer faults reported by OSD records are counted, to pre-screen
+    /// hardware faults without reviewing the whole log.
+    ///
+    /// # Arguments
+    ///
+    /// * `keychains` - An optional vector of vectors containing `KeychainFeaturePoint` instances. This parameter
+    ///   is used for decryption when working with encrypted logs (versions >= 13). If `None` is provided,
+    ///   the function will attempt to process the log without decryption.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<FaultSummary>` of the whole flight, or the error of the first record
+    /// that cannot be decoded.
+    ///
+    pub fn fault_summary(
+        &self,
+        keychains: Option<Vec<Vec<KeychainFeaturePoint>>>,
+    ) -> Result<FaultSummary> {
+        let records = self.record_iter(keychains)?.collect::<Result<Vec<_>>>()?;
+        Ok(FaultSummary::from_records(records))
+    }
+
+    /// Produces a decrypted copy of the log file.
+    ///
+    /// The content of each record is copied byte for byte once Xor decoded and AES decrypted,
+    /// and only Xor encoded in the copy. Logs version 13 and above are written as version 12
+    /// logs, readable without keychains, unless a decrypted record does not fit in the one byte
+    /// record length of version 12. The copy then keeps the version and the Auxiliary Version
+    /// block of the log, and must be read with an empty keychain list. Logs version 12 and below
+    /// are not encrypted, and are copied in their own version.
+    ///
+    /// # Arguments
+    ///
+    /// * `keychains` - An optional vector of vectors containing `KeychainFeaturePoint` instances. This parameter
+    ///   is used for decryption when working with encrypted logs (versions >= 13). If `None` is provided,
+    ///   the function will attempt to process the log without decryption.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<Vec<u8>>` with the bytes of the decrypted log, which can be read back
+    /// with `DJILog::from_bytes`, or the error of the first record that cannot be decoded.
+    /// Records failing the AES padding check return `Error::DecryptionFailed`.
+    ///
+    pub fn decrypt(&self, keychains: Option<Vec<Vec<KeychainFeaturePoint>>>) -> Result<Vec<u8>> {
+        let mut raw_records = self.raw_record_iter(keychains)?;
+        let mut records = Vec::new();
+        for raw_record in raw_records.by_ref() {
+            let raw_record = raw_record?;
+            records.push((raw_record.record_type, raw_record.decrypted));
+        }
+
+        let failures = raw_records.report().decryption_failures();
+        if failures > 0 {
+            return Err(Error::DecryptionFailed(failures));
+        }
+
+        if self.version <= 12 {
+            let writer =
+                DJILogWriter::new(Cursor::new(Vec::new()), self.version, self.details.clone())?;
+            return Ok(write_raw_records(writer, &records)?.into_inner());
+        }
+
+        // Last version without AES encryption
+        let writer = DJILogWriter::new(Cursor::new(Vec::new()), 12, self.details.clone())?;
+        match write_raw_records(writer, &records) {
+            Err(Error::RecordTooLarge(_, _)) => {
+                let auxiliary = self
+                    .auxiliary()?
+                    .into_iter()
+                    .find_map(|block| match block {
+                        Auxiliary::Version(data) => Some(data),
+                        _ => None,
+                    })
+                    .ok_or_else(|| Error::MissingAuxilliaryData("Version".into()))?;
+
+                let writer = DJILogWriter::new_with_auxiliary(
+                    Cursor::new(Vec::new()),
+                    self.version,
+                    self.details.clone(),
+                    auxiliary,
+                )?;
+                Ok(write_raw_records(writer, &records)?.into_inner())
+            }
+            result => Ok(result?.into_inner()),
+        }
+    }
+
+    /// Produces an anonymized copy of the log file.
+    ///
+    /// Records are decoded, scrubbed by the `Anonymizer` and re-encoded with a `DJILogWriter`
+    /// in the same version, so the output can still be read by `DJILog::from_bytes`.
+    ///
+    /// # Arguments
+    ///
+    /// * `anonymizer` - The `Anonymizer` holding the secret coordinate transform.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<Vec<u8>>` with the bytes of the anonymized log, or the error of the first
+    /// record that cannot be decoded. Logs version 13 and above cannot be re-encrypted and return `Error::UnsupportedVersion`,
+    /// use `anonymize_json` instead.
+    ///
+    pub fn anonymize(&self, anonymizer: &Anonymizer) -> Result<Vec<u8>> {
+        if self.version >= 13 {
+            return Err(Error::UnsupportedVersion(self.version));
+        }
+
+        let mut details = self.details.clone();
+        let mut records = self.record_iter(None)?.collect::<Result<Vec<_>>>()?;
+        anonymizer.anonymize(&mut details, &mut records);
+
+        let mut writer = DJILogWriter::new(Cursor::new(Vec::new()), self.version, details)?;
+        for record in &records {
+            writer.write_record(record)?;
+        }
+        Ok(writer.finish()?.into_inner())
+    }
+
+    /// Produces an anonymized JSON export of the log details and records, for all log versions.
+    ///
+    /// # Arguments
+    ///
+    /// * `keychains` - An optional vector of vectors containing `KeychainFeaturePoint` instances. This parameter
+    ///   is used for decryption when working with encrypted logs (versions >= 13). If `None` is provided,
+    ///   the function will attempt to process the log without decryption.
+    /// * `anonymizer` - The `Anonymizer` holding the secret coordinate transform.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<String>` with a JSON object holding the `version`, `details` and `records`
+    /// of the log, or the error of the first record that cannot be decoded.
+    ///
+    pub fn anonymize_json(
+        &self,
+        keychains: Option<Vec<Vec<KeychainFeaturePoint>>>,
+        anonymizer: &Anonymizer,
+    ) -> Result<String> {
+        #[derive(serde::Serialize)]
+        struct AnonymizedLog {
+            version: u8,
+            details: Details,
+            records: Vec<Record>,
+        }
+
+        let mut details = self.details.clone();
+        let mut records = self.record_iter(keychains)?.collect::<Result<Vec<_>>>()?;
+        anonymizer.anonymize(&mut details, &mut records);
+
+        Ok(serde_json::to_string(&AnonymizedLog {
+            version: self.version,
+            details,
+            records,
+        })?)
+    }
+}
+
+/// Fetches keychains asynchronously, through a `KeychainClient` on native targets.
+#[cfg(any(target_arch = "wasm32", feature = "native-async"))]
+async fn fetch_async(
+    api_key: &str,
+    request: &KeychainsRequest,
+) -> Result<Vec<Vec<KeychainFeaturePoint>>> {
+    #[cfg(not(target_arch = "wasm32"))]
+    return KeychainClient::new(api_key).fetch_async(request).await;
+    #[cfg(target_arch = "wasm32")]
+    return request.fetch_async(api_key, None).await;
+}
+
+/// Writes records from their type and decoded content, and completes the log layout.
+/// Records without type, JPEG images and invalid data, are written as they were read.
+fn write_raw_records<W: Write + Seek>(
+    mut writer: DJILogWriter<W>,
+    records: &[(Option<u8>, Vec<u8>)],
+) -> Result<W> {
+    for (record_type, content) in records {
+        match record_type {
+            Some(record_type) => writer.write_raw_record(*record_type, content)?,
+            None => writer.write_record(&Record::Invalid(content.clone()))?,
+        }
+    }
+    writer.finish()
 }
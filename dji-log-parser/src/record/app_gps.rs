@@ -1,12 +1,12 @@
-use binrw::binread;
+use binrw::binrw;
 use serde::Serialize;
 #[cfg(target_arch = "wasm32")]
 use tsify_next::Tsify;
 
-#[binread]
-#[derive(Serialize, Debug)]
+#[binrw]
+#[derive(Serialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
-#[br(little)]
+#[brw(little)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub struct AppGPS {
     /// degrees
@@ -1,14 +1,16 @@
-use binrw::binread;
+use binrw::binrw;
 use serde::Serialize;
 #[cfg(target_arch = "wasm32")]
 use tsify_next::Tsify;
 
-#[binread]
-#[derive(Serialize, Debug)]
+#[binrw]
+#[derive(Serialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
-#[br(little, import { length: u16 })]
+#[brw(little)]
+#[br(import { length: u16 })]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub struct AppTip {
     #[br(count=length, map = |s: Vec<u8>| String::from_utf8_lossy(&s).trim_end_matches('\0').to_string())]
+    #[bw(map = |s: &String| s.as_bytes().to_vec())]
     pub message: String,
 }
@@ -1,67 +1,106 @@
-use binrw::binread;
+use binrw::binrw;
 use serde::Serialize;
 #[cfg(target_arch = "wasm32")]
 use tsify_next::Tsify;
 
-use crate::utils::sub_byte_field;
+use crate::utils::{pack_byte_field, sub_byte_field};
 
-#[binread]
-#[derive(Serialize, Debug)]
+#[binrw]
+#[derive(Serialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
-#[br(little)]
+#[brw(little)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub struct Camera {
     #[br(temp)]
+    #[bw(calc = pack_byte_field(*is_connect as u8, 0x01)
+        | pack_byte_field(*is_usb_connect as u8, 0x02)
+        | pack_byte_field(*timer_sync_state, 0x04)
+        | pack_byte_field(*is_shooting_single_photo as u8, 0x38)
+        | pack_byte_field(*is_recording as u8, 0xC0))]
     _bitpack1: u8,
     #[br(calc(sub_byte_field(_bitpack1, 0x01) == 1))]
+    #[bw(ignore)]
     pub is_connect: bool,
     #[br(calc(sub_byte_field(_bitpack1, 0x02) == 1))]
+    #[bw(ignore)]
     pub is_usb_connect: bool,
     #[br(calc(sub_byte_field(_bitpack1, 0x04)))]
+    #[bw(ignore)]
     pub timer_sync_state: u8,
     #[br(calc(sub_byte_field(_bitpack1, 0x38) == 1))]
+    #[bw(ignore)]
     pub is_shooting_single_photo: bool,
     #[br(calc(sub_byte_field(_bitpack1, 0xC0) != 0))]
+    #[bw(ignore)]
     pub is_recording: bool,
 
     #[br(temp)]
+    #[bw(calc = pack_byte_field(*has_sd_card as u8, 0x02)
+        | pack_byte_field(u8::from(*sd_card_state), 0x3C)
+        | pack_byte_field(*is_upgrading, 0x40))]
     _bitpack2: u8,
     #[br(calc(sub_byte_field(_bitpack2, 0x02) == 1))]
+    #[bw(ignore)]
     pub has_sd_card: bool,
     #[br(calc(SDCardState::from(sub_byte_field(_bitpack2, 0x3C))))]
+    #[bw(ignore)]
     pub sd_card_state: SDCardState,
     #[br(calc(sub_byte_field(_bitpack2, 0x40)))]
+    #[bw(ignore)]
     pub is_upgrading: u8,
 
     #[br(temp)]
+    #[bw(calc = pack_byte_field(*is_heat as u8, 0x02)
+        | pack_byte_field(*is_capture_disable as u8, 0x04)
+        | pack_byte_field(*is_ddr_storing as u8, 0x08)
+        | pack_byte_field(*conti_capture as u8, 0x10)
+        | pack_byte_field(*hdmi_output_status as u8, 0x20)
+        | pack_byte_field(*encrypt_status, 0xC0))]
     _bitpack3: u8,
     #[br(calc(sub_byte_field(_bitpack3, 0x02) == 1))]
+    #[bw(ignore)]
     pub is_heat: bool,
     #[br(calc(sub_byte_field(_bitpack3, 0x04) == 1))]
+    #[bw(ignore)]
     pub is_capture_disable: bool,
     #[br(calc(sub_byte_field(_bitpack3, 0x08) == 1))]
+    #[bw(ignore)]
     pub is_ddr_storing: bool,
     #[br(calc(sub_byte_field(_bitpack3, 0x10) == 1))]
+    #[bw(ignore)]
     pub conti_capture: bool,
     #[br(calc(sub_byte_field(_bitpack3, 0x20) == 1))]
+    #[bw(ignore)]
     pub hdmi_output_status: bool,
     #[br(calc(sub_byte_field(_bitpack3, 0xC0)))]
+    #[bw(ignore)]
     pub encrypt_status: u8,
 
     #[br(temp)]
+    #[bw(calc = pack_byte_field(*file_syn_state as u8, 0x01)
+        | pack_byte_field(*rc_btn_forbid_state as u8, 0x02)
+        | pack_byte_field(*get_focus_state as u8, 0x04)
+        | pack_byte_field(*pano_timelapse_gimbal_state as u8, 0x08)
+        | pack_byte_field(*is_enable_tracking_mode as u8, 0x10))]
     _bitpack4: u8,
     #[br(calc(sub_byte_field(_bitpack4, 0x01) == 1))]
+    #[bw(ignore)]
     pub file_syn_state: bool,
     #[br(calc(sub_byte_field(_bitpack4, 0x02) == 1))]
+    #[bw(ignore)]
     pub rc_btn_forbid_state: bool,
     #[br(calc(sub_byte_field(_bitpack4, 0x04) == 1))]
+    #[bw(ignore)]
     pub get_focus_state: bool,
     #[br(calc(sub_byte_field(_bitpack4, 0x08) == 1))]
+    #[bw(ignore)]
     pub pano_timelapse_gimbal_state: bool,
     #[br(calc(sub_byte_field(_bitpack4, 0x10) == 1))]
+    #[bw(ignore)]
     pub is_enable_tracking_mode: bool,
 
     #[br(map = |x: u8| CameraWorkMode::from(x))]
+    #[bw(map = |x: &CameraWorkMode| u8::from(*x))]
     pub work_mode: CameraWorkMode,
     /// MB
     pub sd_card_total_capacity: u32,
@@ -75,9 +114,10 @@ pub struct Camera {
     pub camera_type: u8,
 }
 
-#[derive(Serialize, Debug, Clone, Copy)]
+#[derive(Serialize, Debug, Clone, Copy, Default)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub enum SDCardState {
+    #[default]
     Normal,
     NoCard,
     InvalidCard,
@@ -117,9 +157,32 @@ impl From<u8> for SDCardState {
     }
 }
 
-#[derive(Serialize, Debug)]
+impl From<SDCardState> for u8 {
+    fn from(value: SDCardState) -> Self {
+        match value {
+            SDCardState::Normal => 0,
+            SDCardState::NoCard => 1,
+            SDCardState::InvalidCard => 2,
+            SDCardState::WriteProtected => 3,
+            SDCardState::Unformatted => 4,
+            SDCardState::Formatting => 5,
+            SDCardState::IllegalFileSys => 6,
+            SDCardState::Busy => 7,
+            SDCardState::Full => 8,
+            SDCardState::LowSpeed => 9,
+            SDCardState::IndexMax => 11,
+            SDCardState::Initialize => 12,
+            SDCardState::SuggestFormat => 13,
+            SDCardState::Repairing => 14,
+            SDCardState::Unknown(value) => value,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Copy, Default)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub enum CameraWorkMode {
+    #[default]
     Capture,
     Recording,
     Playback,
@@ -149,3 +212,20 @@ impl From<u8> for CameraWorkMode {
         }
     }
 }
+
+impl From<CameraWorkMode> for u8 {
+    fn from(value: CameraWorkMode) -> Self {
+        match value {
+            CameraWorkMode::Capture => 0,
+            CameraWorkMode::Recording => 1,
+            CameraWorkMode::Playback => 2,
+            CameraWorkMode::Transcode => 3,
+            CameraWorkMode::Tuning => 4,
+            CameraWorkMode::PowerSave => 5,
+            CameraWorkMode::Download => 6,
+            CameraWorkMode::XcodePlayback => 7,
+            CameraWorkMode::Broadcast => 8,
+            CameraWorkMode::Unknown(value) => value,
+        }
+    }
+}
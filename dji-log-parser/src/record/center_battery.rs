@@ -1,18 +1,19 @@
-use binrw::binread;
+use binrw::binrw;
 use serde::Serialize;
 #[cfg(target_arch = "wasm32")]
 use tsify_next::Tsify;
 
-use crate::utils::sub_byte_field;
+use crate::utils::{pack_byte_field, sub_byte_field};
 
-#[binread]
-#[derive(Serialize, Debug)]
+#[binrw]
+#[derive(Serialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
-#[br(little, import { version: u8 })]
+#[brw(little, import { version: u8 })]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub struct CenterBattery {
     pub relative_capacity: u8,
     #[br(map = |x: u16| x as f32 / 1000.0)]
+    #[bw(map = |x: &f32| (x * 1000.0).round() as u16)]
     pub voltage: f32,
     pub current_capacity: u16,
     pub full_capacity: u16,
@@ -20,24 +21,32 @@ pub struct CenterBattery {
     pub number_of_discharges: u16,
     pub error_type: u32,
     #[br(map = |x: i16| x as f32 / 1000.0)]
+    #[bw(map = |x: &f32| (x * 1000.0).round() as i16)]
     pub current: f32,
 
     #[br(map = |x: u16| x as f32 / 1000.0)]
+    #[bw(map = |x: &f32| (x * 1000.0).round() as u16)]
     pub voltage_cell1: f32,
     #[br(map = |x: u16| x as f32 / 1000.0)]
+    #[bw(map = |x: &f32| (x * 1000.0).round() as u16)]
     pub voltage_cell2: f32,
     #[br(map = |x: u16| x as f32 / 1000.0)]
+    #[bw(map = |x: &f32| (x * 1000.0).round() as u16)]
     pub voltage_cell3: f32,
     #[br(map = |x: u16| x as f32 / 1000.0)]
+    #[bw(map = |x: &f32| (x * 1000.0).round() as u16)]
     pub voltage_cell4: f32,
     #[br(map = |x: u16| x as f32 / 1000.0)]
+    #[bw(map = |x: &f32| (x * 1000.0).round() as u16)]
     pub voltage_cell5: f32,
     #[br(map = |x: u16| x as f32 / 1000.0)]
+    #[bw(map = |x: &f32| (x * 1000.0).round() as u16)]
     pub voltage_cell6: f32,
 
     pub serial_number: u16,
     pub product_date: u16,
     #[br(if(version >=8), map = |x: u16| x as f32 / 10.0 - 273.15)]
+    #[bw(if(version >=8), map = |x: &f32| ((x + 273.15) * 10.0).round() as u16)]
     pub temperature: f32,
     #[br(if(version >=8))]
     pub connect_state: u8,
@@ -47,9 +56,12 @@ pub struct CenterBattery {
     pub latest_learn_cycle: u16,
 
     #[br(if(version >=8), temp)]
+    #[bw(if(version >=8), calc = pack_byte_field(*battery_on_charge as u8, 0x01) | pack_byte_field(*reverse, 0xFE))]
     _bitpack1: u8,
     #[br(calc(sub_byte_field(_bitpack1, 0x01) == 1))]
+    #[bw(ignore)]
     pub battery_on_charge: bool,
     #[br(calc(sub_byte_field(_bitpack1, 0xFE)))]
+    #[bw(ignore)]
     pub reverse: u8,
 }
@@ -1,24 +1,28 @@
-use binrw::binread;
+use binrw::binrw;
 use serde::Serialize;
 #[cfg(target_arch = "wasm32")]
 use tsify_next::Tsify;
 
-#[binread]
-#[derive(Serialize, Debug)]
+#[binrw]
+#[derive(Serialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub struct ComponentSerial {
     #[br(map = |x: u16| ComponentType::from(x as u8))]
+    #[bw(map = |x: &ComponentType| u8::from(*x) as u16)]
     pub component_type: ComponentType,
     #[br(temp)]
+    #[bw(calc = serial.len() as u8)]
     length: u8,
     #[br(count=length, map = |s: Vec<u8>| String::from_utf8_lossy(&s).trim_end_matches('\0').to_string())]
+    #[bw(map = |s: &String| s.as_bytes().to_vec())]
     pub serial: String,
 }
 
-#[derive(Serialize, Debug, Clone, Copy)]
+#[derive(Serialize, Debug, Clone, Copy, Default)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub enum ComponentType {
+    #[default]
     Camera,
     Aircraft,
     RC,
@@ -38,3 +42,15 @@ impl ComponentType {
         }
     }
 }
+
+impl From<ComponentType> for u8 {
+    fn from(value: ComponentType) -> Self {
+        match value {
+            ComponentType::Camera => 1,
+            ComponentType::Aircraft => 2,
+            ComponentType::RC => 3,
+            ComponentType::Battery => 4,
+            ComponentType::Unknown(value) => value,
+        }
+    }
+}
@@ -1,18 +1,20 @@
-use binrw::binread;
+use binrw::binrw;
 use chrono::{DateTime, Datelike, Utc};
 use serde::Serialize;
 #[cfg(target_arch = "wasm32")]
 use tsify_next::Tsify;
 
-#[binread]
-#[derive(Serialize, Debug)]
+#[binrw]
+#[derive(Serialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
-#[br(little)]
+#[brw(little)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub struct Custom {
     #[br(temp)]
+    #[bw(calc = 0)]
     _camera_shoot: u8,
     #[br(temp)]
+    #[bw(calc = 0)]
     _video_shoot: u8,
     pub h_speed: f32,
     pub distance: f32,
@@ -21,6 +23,7 @@ pub struct Custom {
         // We ensure the year is between 2010 and 2100 to avoid invalid data
         assert(update_timestamp.year() > 2010 && update_timestamp.year() < 2100)
     )]
+    #[bw(map = |x: &DateTime<Utc>| x.timestamp_millis())]
     #[cfg_attr(target_arch = "wasm32", tsify(type = "string"))]
     pub update_timestamp: DateTime<Utc>,
 }
@@ -1,29 +1,36 @@
-use binrw::binread;
+use binrw::binrw;
 use serde::Serialize;
 #[cfg(target_arch = "wasm32")]
 use tsify_next::Tsify;
 
-use crate::utils::sub_byte_field;
+use crate::utils::{pack_byte_field, sub_byte_field};
 
-#[binread]
-#[derive(Serialize, Debug)]
+#[binrw]
+#[derive(Serialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
-#[br(little)]
+#[brw(little)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub struct Deform {
     #[br(temp)]
+    #[bw(calc = pack_byte_field(*is_deform_protected as u8, 0x01)
+        | pack_byte_field(u8::from(*deform_status), 0x0E)
+        | pack_byte_field(u8::from(*deform_mode), 0x30))]
     _bitpack1: u8,
     #[br(calc(sub_byte_field(_bitpack1, 0x01) == 1))]
+    #[bw(ignore)]
     pub is_deform_protected: bool,
     #[br(calc(DeformStatus::from(sub_byte_field(_bitpack1, 0x0E))))]
+    #[bw(ignore)]
     pub deform_status: DeformStatus,
     #[br(calc(DeformMode::from(sub_byte_field(_bitpack1, 0x30))))]
+    #[bw(ignore)]
     pub deform_mode: DeformMode,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, Clone, Copy, Default)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub enum DeformMode {
+    #[default]
     Pack,
     Protect,
     Normal,
@@ -42,9 +49,21 @@ impl From<u8> for DeformMode {
     }
 }
 
-#[derive(Serialize, Debug)]
+impl From<DeformMode> for u8 {
+    fn from(value: DeformMode) -> Self {
+        match value {
+            DeformMode::Pack => 0,
+            DeformMode::Protect => 1,
+            DeformMode::Normal => 2,
+            DeformMode::Unknown(value) => value,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Copy, Default)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub enum DeformStatus {
+    #[default]
     FoldComplete,
     Folding,
     StretchComplete,
@@ -66,3 +85,16 @@ impl From<u8> for DeformStatus {
         }
     }
 }
+
+impl From<DeformStatus> for u8 {
+    fn from(value: DeformStatus) -> Self {
+        match value {
+            DeformStatus::FoldComplete => 1,
+            DeformStatus::Folding => 2,
+            DeformStatus::StretchComplete => 3,
+            DeformStatus::Stretching => 4,
+            DeformStatus::StopDeformation => 5,
+            DeformStatus::Unknown(value) => value,
+        }
+    }
+}
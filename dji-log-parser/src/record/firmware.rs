@@ -1,24 +1,29 @@
-use binrw::binread;
+use binrw::binrw;
 use serde::Serialize;
 #[cfg(target_arch = "wasm32")]
 use tsify_next::Tsify;
 
-#[binread]
-#[derive(Serialize, Debug)]
+use crate::utils::version_to_bytes;
+
+#[binrw]
+#[derive(Serialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
-#[br(little)]
+#[brw(little)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub struct Firmware {
     #[br(map = |x: u8| SenderType::from(x))]
+    #[bw(map = |x: &SenderType| u8::from(*x))]
     pub sender_type: SenderType,
     pub sub_sender_type: u8,
     #[br(map = |x: [u8; 4]| format!("{}.{}.{}", x[0], x[1], x[2]))]
+    #[bw(map = |x: &String| version_to_bytes(x, 4))]
     pub version: String,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, Clone, Copy, Default)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub enum SenderType {
+    #[default]
     None,
     Camera,
     MC,
@@ -41,3 +46,17 @@ impl SenderType {
         }
     }
 }
+
+impl From<SenderType> for u8 {
+    fn from(value: SenderType) -> Self {
+        match value {
+            SenderType::None => 0,
+            SenderType::Camera => 1,
+            SenderType::MC => 3,
+            SenderType::Gimbal => 4,
+            SenderType::RC => 6,
+            SenderType::Battery => 11,
+            SenderType::Unknown(value) => value,
+        }
+    }
+}
@@ -1,72 +1,106 @@
-use crate::utils::sub_byte_field;
-use binrw::binread;
+use crate::utils::{pack_byte_field, sub_byte_field};
+use binrw::binrw;
 use serde::Serialize;
 #[cfg(target_arch = "wasm32")]
 use tsify_next::Tsify;
 
-#[binread]
-#[derive(Serialize, Debug)]
+#[binrw]
+#[derive(Serialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
-#[br(import { version: u8 }, little)]
+#[brw(little)]
+#[br(import { version: u8 })]
+// The log version is renamed on write to not be shadowed by the gimbal `version` field
+#[bw(import { log_version: u8 })]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub struct Gimbal {
     /// degrees
     #[br(map = |x: i16| (x as f32 / 10.0))]
+    #[bw(map = |x: &f32| (x * 10.0).round() as i16)]
     pub pitch: f32,
     /// degrees
     #[br(map = |x: i16| (x as f32 / 10.0))]
+    #[bw(map = |x: &f32| (x * 10.0).round() as i16)]
     pub roll: f32,
     /// degrees
     #[br(map = |x: i16| (x as f32 / 10.0))]
+    #[bw(map = |x: &f32| (x * 10.0).round() as i16)]
     pub yaw: f32,
 
     #[br(temp)]
+    #[bw(calc = pack_byte_field(u8::from(*mode), 0xC0) | pack_byte_field(*reset, 0x20))]
     _bitpack1: u8,
     #[br(calc(GimbalMode::from(sub_byte_field(_bitpack1, 0xC0))))]
+    #[bw(ignore)]
     pub mode: GimbalMode,
     #[br(calc(sub_byte_field(_bitpack1, 0x20)))]
+    #[bw(ignore)]
     pub reset: u8,
 
     #[br(map = |x: i8| (x as f32 / 10.0))]
+    #[bw(map = |x: &f32| (x * 10.0).round() as i8)]
     pub roll_adjust: f32,
     #[br(map = |x: i16| (x as f32 / 10.0))]
+    #[bw(map = |x: &f32| (x * 10.0).round() as i16)]
     pub yaw_angle: f32,
 
     #[br(temp)]
+    #[bw(calc = pack_byte_field(*is_pitch_at_limit as u8, 0x01)
+        | pack_byte_field(*is_roll_at_limit as u8, 0x02)
+        | pack_byte_field(*is_yaw_at_limit as u8, 0x04)
+        | pack_byte_field(*is_auto_calibration as u8, 0x08)
+        | pack_byte_field(*auto_calibration_result as u8, 0x10)
+        | pack_byte_field(*install_direction as u8, 0x20)
+        | pack_byte_field(*is_stuck as u8, 0x40))]
     _bitpack2: u8,
     #[br(calc(sub_byte_field(_bitpack2, 0x01) == 1))]
+    #[bw(ignore)]
     pub is_pitch_at_limit: bool,
     #[br(calc(sub_byte_field(_bitpack2, 0x02) == 1))]
+    #[bw(ignore)]
     pub is_roll_at_limit: bool,
     #[br(calc(sub_byte_field(_bitpack2, 0x04) == 1))]
+    #[bw(ignore)]
     pub is_yaw_at_limit: bool,
     #[br(calc(sub_byte_field(_bitpack2, 0x08) == 1))]
+    #[bw(ignore)]
     pub is_auto_calibration: bool,
     #[br(calc(sub_byte_field(_bitpack2, 0x10) == 1))]
+    #[bw(ignore)]
     pub auto_calibration_result: bool,
     #[br(calc(sub_byte_field(_bitpack2, 0x20) == 1))]
+    #[bw(ignore)]
     pub install_direction: bool,
     #[br(calc(sub_byte_field(_bitpack2, 0x40) == 1))]
+    #[bw(ignore)]
     pub is_stuck: bool,
     #[br(if(version >=2), temp)]
+    #[bw(if(log_version >=2), calc = pack_byte_field(*version, 0x0F)
+        | pack_byte_field(*is_double_click as u8, 0x20)
+        | pack_byte_field(*is_triple_click as u8, 0x40)
+        | pack_byte_field(*is_single_click as u8, 0x80))]
     _bitpack3: u8,
     #[br(calc(sub_byte_field(_bitpack3, 0x0F)))]
+    #[bw(ignore)]
     pub version: u8,
     #[br(calc(sub_byte_field(_bitpack3, 0x20) == 1))]
+    #[bw(ignore)]
     pub is_double_click: bool,
     #[br(calc(sub_byte_field(_bitpack3, 0x40) == 1))]
+    #[bw(ignore)]
     pub is_triple_click: bool,
     #[br(calc(sub_byte_field(_bitpack3, 0x80) == 1))]
+    #[bw(ignore)]
     pub is_single_click: bool,
 }
 
-#[derive(Serialize, Debug, Clone, Copy)]
+#[derive(Serialize, Debug, Clone, Copy, Default)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub enum GimbalMode {
     /// The gimbal can move independently of the aircraft's yaw. In this mode, even if
     /// the aircraft yaw changes, the camera will continue pointing in the same world
     /// direction. This mode is only available for the Ronin-MX when the M600 or M600
     /// Pro landing gear is retracted.
+    #[default]
     Free,
     /// The gimbal's work mode is FPV mode. In this mode, the gimbal yaw will follow the
     /// aircraft's heading, and the gimbal roll will follow the RC's roll channel value.
@@ -90,3 +124,14 @@ impl From<u8> for GimbalMode {
         }
     }
 }
+
+impl From<GimbalMode> for u8 {
+    fn from(value: GimbalMode) -> Self {
+        match value {
+            GimbalMode::Free => 0,
+            GimbalMode::FPV => 1,
+            GimbalMode::YawFollow => 2,
+            GimbalMode::Unknown(value) => value,
+        }
+    }
+}
@@ -1,57 +1,86 @@
-use binrw::binread;
+use binrw::binrw;
 use serde::Serialize;
 use std::f64::consts::PI;
 #[cfg(target_arch = "wasm32")]
 use tsify_next::Tsify;
 
-use crate::utils::sub_byte_field;
+use crate::utils::{pack_byte_field, sub_byte_field};
 
-#[binread]
-#[derive(Serialize, Debug)]
+#[binrw]
+#[derive(Serialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
-#[br(little, import { version: u8 })]
+#[brw(little, import { version: u8 })]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub struct Home {
     /// degrees
     #[br(map = |x: f64| (x * 180.0) / PI)]
+    #[bw(map = |x: &f64| (x * PI) / 180.0)]
     pub longitude: f64,
     /// degrees
     #[br(map = |x: f64| (x * 180.0) / PI)]
+    #[bw(map = |x: &f64| (x * PI) / 180.0)]
     pub latitude: f64,
     /// meters
     #[br(map = |x: f32| (x / 10.0))]
+    #[bw(map = |x: &f32| x * 10.0)]
     pub altitude: f32,
 
     #[br(temp)]
+    #[bw(calc = pack_byte_field(*is_home_record as u8, 0x01)
+        | pack_byte_field(bool::from(*go_home_mode) as u8, 0x02)
+        | pack_byte_field(*aircraft_head_direction, 0x04)
+        | pack_byte_field(*is_dynamic_home_point_enabled as u8, 0x08)
+        | pack_byte_field(*is_near_distance_limit as u8, 0x10)
+        | pack_byte_field(*is_near_height_limit as u8, 0x20)
+        | pack_byte_field(*is_multiple_mode_open as u8, 0x40)
+        | pack_byte_field(*has_go_home as u8, 0x80))]
     _bitpack1: u8,
     #[br(calc(sub_byte_field(_bitpack1, 0x01) == 1))]
+    #[bw(ignore)]
     pub is_home_record: bool,
     #[br(calc(GoHomeMode::from(sub_byte_field(_bitpack1, 0x02) == 1)))]
+    #[bw(ignore)]
     pub go_home_mode: GoHomeMode,
     #[br(calc(sub_byte_field(_bitpack1, 0x04)))]
+    #[bw(ignore)]
     pub aircraft_head_direction: u8,
     #[br(calc(sub_byte_field(_bitpack1, 0x08) == 1))]
+    #[bw(ignore)]
     pub is_dynamic_home_point_enabled: bool,
     #[br(calc(sub_byte_field(_bitpack1, 0x10) == 1))]
+    #[bw(ignore)]
     pub is_near_distance_limit: bool,
     #[br(calc(sub_byte_field(_bitpack1, 0x20) == 1))]
+    #[bw(ignore)]
     pub is_near_height_limit: bool,
     #[br(calc(sub_byte_field(_bitpack1, 0x40) == 1))]
+    #[bw(ignore)]
     pub is_multiple_mode_open: bool,
     #[br(calc(sub_byte_field(_bitpack1, 0x80) == 1))]
+    #[bw(ignore)]
     pub has_go_home: bool,
 
     #[br(temp)]
+    #[bw(calc = pack_byte_field(u8::from(*compass_state), 0x03)
+        | pack_byte_field(*is_compass_adjust as u8, 0x04)
+        | pack_byte_field(*is_beginner_mode as u8, 0x08)
+        | pack_byte_field(*is_ioc_open as u8, 0x10)
+        | pack_byte_field(u8::from(*ioc_mode), 0xE0))]
     _bitpack2: u8,
     #[br(calc(CompassCalibrationState::from(sub_byte_field(_bitpack2, 0x03))))]
+    #[bw(ignore)]
     pub compass_state: CompassCalibrationState,
     #[br(calc(sub_byte_field(_bitpack2, 0x04) == 1))]
+    #[bw(ignore)]
     pub is_compass_adjust: bool,
     #[br(calc(sub_byte_field(_bitpack2, 0x08) == 1))]
+    #[bw(ignore)]
     pub is_beginner_mode: bool,
     #[br(calc(sub_byte_field(_bitpack2, 0x10) == 1))]
+    #[bw(ignore)]
     pub is_ioc_open: bool,
     #[br(calc(IOCMode::from(sub_byte_field(_bitpack2, 0xE0))))]
+    #[bw(ignore)]
     pub ioc_mode: IOCMode,
 
     pub go_home_height: u16,
@@ -61,14 +90,16 @@ pub struct Home {
     pub record_sd_left_time: u16,
     pub current_flight_record_index: u16,
     #[br(if(version >= 8), temp)]
+    #[bw(if(version >= 8), calc = [0; 5])]
     _unknown: [u8; 5],
     #[br(if(version >= 8))]
     pub max_allowed_height: f32,
 }
 
-#[derive(Serialize, Debug, Clone, Copy)]
+#[derive(Serialize, Debug, Clone, Copy, Default)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub enum IOCMode {
+    #[default]
     CourseLock,
     HomeLock,
     HotspotSurround,
@@ -87,9 +118,21 @@ impl From<u8> for IOCMode {
     }
 }
 
-#[derive(Serialize, Debug, Clone, Copy)]
+impl From<IOCMode> for u8 {
+    fn from(value: IOCMode) -> Self {
+        match value {
+            IOCMode::CourseLock => 1,
+            IOCMode::HomeLock => 2,
+            IOCMode::HotspotSurround => 3,
+            IOCMode::Unknown(value) => value,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Copy, Default)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub enum GoHomeMode {
+    #[default]
     Normal,
     FixedHeight,
 }
@@ -103,9 +146,16 @@ impl From<bool> for GoHomeMode {
     }
 }
 
-#[derive(Serialize, Debug, Clone, Copy)]
+impl From<GoHomeMode> for bool {
+    fn from(value: GoHomeMode) -> Self {
+        matches!(value, GoHomeMode::FixedHeight)
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Copy, Default)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub enum CompassCalibrationState {
+    #[default]
     NotCalibrating,
     Horizontal,
     Vertical,
@@ -126,3 +176,16 @@ impl From<u8> for CompassCalibrationState {
         }
     }
 }
+
+impl From<CompassCalibrationState> for u8 {
+    fn from(value: CompassCalibrationState) -> Self {
+        match value {
+            CompassCalibrationState::NotCalibrating => 0,
+            CompassCalibrationState::Horizontal => 1,
+            CompassCalibrationState::Vertical => 2,
+            CompassCalibrationState::Successful => 3,
+            CompassCalibrationState::Failed => 4,
+            CompassCalibrationState::Unnown(value) => value,
+        }
+    }
+}
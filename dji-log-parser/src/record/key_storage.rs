@@ -1,18 +1,19 @@
-use binrw::binread;
+use binrw::binrw;
 use serde::Serialize;
 #[cfg(target_arch = "wasm32")]
 use tsify_next::Tsify;
 
 use crate::keychain::FeaturePoint;
 
-#[binread]
+#[binrw]
 #[derive(Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
-#[br(little)]
+#[brw(little)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub struct KeyStorage {
     pub feature_point: FeaturePoint,
     #[br(temp)]
+    #[bw(calc = data.len() as u16)]
     data_length: u16,
     #[br(count = data_length)]
     pub data: Vec<u8>,
@@ -1,32 +1,40 @@
-use binrw::binread;
+use binrw::binrw;
 use serde::Serialize;
 #[cfg(target_arch = "wasm32")]
 use tsify_next::Tsify;
 
-use crate::utils::sub_byte_field;
+use crate::utils::{pack_byte_field, sub_byte_field};
 
-#[binread]
-#[derive(Serialize, Debug)]
+#[binrw]
+#[derive(Serialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
-#[br(little)]
+#[brw(little)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub struct MCParams {
     #[br(map = |x:u8| FailSafeProtectionType::from(x))]
+    #[bw(map = |x: &FailSafeProtectionType| u8::from(*x))]
     pub fail_safe_protection: FailSafeProtectionType,
 
     #[br(temp)]
+    #[bw(calc = pack_byte_field(*mvo_func_enabled as u8, 0x01)
+        | pack_byte_field(*avoid_obstacle_enabled as u8, 0x02)
+        | pack_byte_field(*user_avoid_enabled as u8, 0x04))]
     _bitpack1: u8,
     #[br(calc(sub_byte_field(_bitpack1, 0x01) == 1))]
+    #[bw(ignore)]
     pub mvo_func_enabled: bool,
     #[br(calc(sub_byte_field(_bitpack1, 0x02) == 1))]
+    #[bw(ignore)]
     pub avoid_obstacle_enabled: bool,
     #[br(calc(sub_byte_field(_bitpack1, 0x04) == 1))]
+    #[bw(ignore)]
     pub user_avoid_enabled: bool,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, Clone, Copy, Default)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub enum FailSafeProtectionType {
+    #[default]
     Hover,
     Landing,
     GoHome,
@@ -44,3 +52,14 @@ impl FailSafeProtectionType {
         }
     }
 }
+
+impl From<FailSafeProtectionType> for u8 {
+    fn from(value: FailSafeProtectionType) -> Self {
+        match value {
+            FailSafeProtectionType::Hover => 0,
+            FailSafeProtectionType::Landing => 1,
+            FailSafeProtectionType::GoHome => 2,
+            FailSafeProtectionType::Unknown(value) => value,
+        }
+    }
+}
@@ -1,6 +1,7 @@
-use binrw::binread;
+use binrw::{binread, BinResult, BinWrite};
 use serde::Serialize;
 use std::cell::RefCell;
+use std::io::Cursor;
 #[cfg(target_arch = "wasm32")]
 use tsify_next::Tsify;
 
@@ -32,6 +33,7 @@ pub mod recover;
 pub mod smart_battery;
 pub mod smart_battery_group;
 pub mod virtual_stick;
+pub mod vision_warn;
 
 use app_gps::AppGPS;
 use app_serious_warn::AppSeriousWarn;
@@ -56,8 +58,17 @@ use recover::Recover;
 use smart_battery::SmartBattery;
 use smart_battery_group::*;
 use virtual_stick::VirtualStick;
+use vision_warn::VisionWarn;
 
-const END_BYTE: u8 = 0xFF;
+pub(crate) const END_BYTE: u8 = 0xFF;
+
+/// Record types known to be found in DJI logs, whether they are decoded or not.
+/// See `FeaturePoint::from_record_type` for the name of each type.
+pub(crate) const RECORD_TYPES: [u8; 57] = [
+    1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 24, 25, 26, 27,
+    28, 29, 30, 31, 32, 33, 34, 35, 36, 38, 39, 40, 41, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53,
+    54, 55, 56, 58, 59, 62, 63,
+];
 
 /// Represents the different types of records.
 ///
@@ -162,7 +173,7 @@ pub enum Record {
         #[br(
             pad_size_to = self_0,
             map_stream = |reader| record_decoder(reader, 9, version, keychain, self_0),
-            args { length: self_0 }
+            args { length: if version <= 6 { self_0 } else { self_0.saturating_sub(2) } }
         )]
         AppTip,
         #[br(temp, assert(self_2 == END_BYTE))] u8,
@@ -173,7 +184,7 @@ pub enum Record {
         #[br(
             pad_size_to = self_0,
             map_stream = |reader| record_decoder(reader, 10, version, keychain, self_0),
-            args { length: self_0 }
+            args { length: if version <= 6 { self_0 } else { self_0.saturating_sub(2) } }
         )]
         AppWarn,
         #[br(temp, assert(self_2 == END_BYTE))] u8,
@@ -219,6 +230,17 @@ pub enum Record {
         Firmware,
         #[br(temp, assert(self_2 == END_BYTE))] u8,
     ),
+    #[br(magic = 18u8)]
+    VisionWarn(
+        #[br(temp, args(version <= 12), parse_with = utils::read_u16)] u16,
+        #[br(
+            pad_size_to = self_0,
+            map_stream = |reader| record_decoder(reader, 18, version, keychain, self_0),
+            args { length: if version <= 6 { self_0 } else { self_0.saturating_sub(2) } }
+        )]
+        VisionWarn,
+        #[br(temp, assert(self_2 == END_BYTE))] u8,
+    ),
     #[br(magic = 19u8)]
     MCParams(
         #[br(temp, args(version <= 12), parse_with = utils::read_u16)] u16,
@@ -245,7 +267,7 @@ pub enum Record {
         #[br(
             pad_size_to = self_0,
             map_stream = |reader| record_decoder(reader, 24, version, keychain, self_0),
-            args { length: self_0 }
+            args { length: if version <= 6 { self_0 } else { self_0.saturating_sub(2) } }
         )]
         AppSeriousWarn,
         #[br(temp, assert(self_2 == END_BYTE))] u8,
@@ -336,3 +358,95 @@ pub enum Record {
     // Invalid data, try to seek to next record
     Invalid(#[br(parse_with = utils::seek_to_next_record, assert(!self_0.is_empty()))] Vec<u8>),
 }
+
+impl Record {
+    /// Returns the type of the record, as found in the first byte of the record in log files.
+    ///
+    /// # Returns
+    ///
+    /// The record type, or `None` for JPEG images and invalid data which are not framed as records.
+    pub fn record_type(&self) -> Option<u8> {
+        match self {
+            Record::OSD(_) => Some(1),
+            Record::Home(_) => Some(2),
+            Record::Gimbal(_) => Some(3),
+            Record::RC(_) => Some(4),
+            Record::Custom(_) => Some(5),
+            Record::Deform(_) => Some(6),
+            Record::CenterBattery(_) => Some(7),
+            Record::SmartBattery(_) => Some(8),
+            Record::AppTip(_) => Some(9),
+            Record::AppWarn(_) => Some(10),
+            Record::RCGPS(_) => Some(11),
+            Record::Recover(_) => Some(13),
+            Record::AppGPS(_) => Some(14),
+            Record::Firmware(_) => Some(15),
+            Record::VisionWarn(_) => Some(18),
+            Record::MCParams(_) => Some(19),
+            Record::SmartBatteryGroup(_) => Some(22),
+            Record::AppSeriousWarn(_) => Some(24),
+            Record::Camera(_) => Some(25),
+            Record::VirtualStick(_) => Some(33),
+            Record::ComponentSerial(_) => Some(40),
+            Record::OFDM(_) => Some(49),
+            Record::KeyStorageRecover(_) => Some(50),
+            Record::KeyStorage(_) => Some(56),
+            Record::RCDisplayField(_) => Some(62),
+            Record::JPEG(_) => None,
+            Record::Unknown(record_type, _) => Some(*record_type),
+            Record::Invalid(_) => None,
+        }
+    }
+
+    /// Encodes the record content, before any Xor or AES encoding.
+    ///
+    /// JPEG images, invalid data and records without a known layout are returned as they were read.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - The log version the content is encoded for.
+    ///
+    /// # Returns
+    ///
+    /// The content bytes, without the record type, length and end byte.
+    pub fn content(&self, version: u8) -> BinResult<Vec<u8>> {
+        let mut writer = Cursor::new(Vec::new());
+
+        match self {
+            Record::OSD(data) => data.write_args(&mut writer, binrw::args! { version })?,
+            Record::Home(data) => data.write_args(&mut writer, binrw::args! { version })?,
+            Record::Gimbal(data) => {
+                data.write_args(&mut writer, binrw::args! { log_version: version })?
+            }
+            Record::RC(data) => data.write_args(&mut writer, binrw::args! { version })?,
+            Record::Custom(data) => data.write(&mut writer)?,
+            Record::Deform(data) => data.write(&mut writer)?,
+            Record::CenterBattery(data) => {
+                data.write_args(&mut writer, binrw::args! { version })?
+            }
+            Record::SmartBattery(data) => data.write(&mut writer)?,
+            Record::AppTip(data) => data.write(&mut writer)?,
+            Record::AppWarn(data) => data.write(&mut writer)?,
+            Record::RCGPS(data) => data.write(&mut writer)?,
+            Record::Recover(data) => data.write_args(&mut writer, binrw::args! { version })?,
+            Record::AppGPS(data) => data.write(&mut writer)?,
+            Record::Firmware(data) => data.write(&mut writer)?,
+            Record::VisionWarn(data) => data.write(&mut writer)?,
+            Record::MCParams(data) => data.write(&mut writer)?,
+            Record::SmartBatteryGroup(data) => data.write(&mut writer)?,
+            Record::AppSeriousWarn(data) => data.write(&mut writer)?,
+            Record::Camera(data) => data.write(&mut writer)?,
+            Record::VirtualStick(data) => data.write(&mut writer)?,
+            Record::ComponentSerial(data) => data.write_le(&mut writer)?,
+            Record::OFDM(data) => data.write(&mut writer)?,
+            Record::KeyStorage(data) => data.write(&mut writer)?,
+            Record::RCDisplayField(data) => data.write(&mut writer)?,
+            Record::KeyStorageRecover(data)
+            | Record::JPEG(data)
+            | Record::Unknown(_, data)
+            | Record::Invalid(data) => return Ok(data.clone()),
+        }
+
+        Ok(writer.into_inner())
+    }
+}
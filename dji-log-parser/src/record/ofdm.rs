@@ -1,20 +1,23 @@
-use binrw::binread;
+use binrw::binrw;
 use serde::Serialize;
 #[cfg(target_arch = "wasm32")]
 use tsify_next::Tsify;
 
-use crate::utils::sub_byte_field;
+use crate::utils::{pack_byte_field, sub_byte_field};
 
-#[binread]
-#[derive(Serialize, Debug)]
+#[binrw]
+#[derive(Serialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
-#[br(little)]
+#[brw(little)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub struct OFDM {
     #[br(temp)]
+    #[bw(calc = pack_byte_field(*signal_percent, 0x7F) | pack_byte_field(*is_up as u8, 0x80))]
     _bitpack1: u8,
     #[br(calc(sub_byte_field(_bitpack1, 0x7F)))]
+    #[bw(ignore)]
     pub signal_percent: u8,
     #[br(calc(sub_byte_field(_bitpack1, 0x80) == 1))]
+    #[bw(ignore)]
     pub is_up: bool,
 }
@@ -1,138 +1,205 @@
-use binrw::binread;
+use binrw::binrw;
 use serde::Serialize;
 use std::f64::consts::PI;
 #[cfg(target_arch = "wasm32")]
 use tsify_next::Tsify;
 
-use crate::utils::sub_byte_field;
+use crate::utils::{pack_byte_field, sub_byte_field};
 
-#[binread]
-#[derive(Serialize, Debug)]
+#[binrw]
+#[derive(Serialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
-#[br( import { version: u8 }, little)]
+#[brw(little, import { version: u8 })]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub struct OSD {
     /// degrees
     #[br(map = |x: f64| (x * 180.0) / PI)]
+    #[bw(map = |x: &f64| (x * PI) / 180.0)]
     pub longitude: f64,
     /// degrees
     #[br(map = |x: f64| (x * 180.0) / PI)]
+    #[bw(map = |x: &f64| (x * PI) / 180.0)]
     pub latitude: f64,
     /// meters
     #[br(map = |x: i16| (x as f32 / 10.0))]
+    #[bw(map = |x: &f32| (x * 10.0).round() as i16)]
     pub altitude: f32,
     /// meters / sec
     #[br(map = |x: i16| (x as f32 / 10.0))]
+    #[bw(map = |x: &f32| (x * 10.0).round() as i16)]
     pub speed_x: f32,
     /// meters / sec
     #[br(map = |x: i16| (x as f32 / 10.0))]
+    #[bw(map = |x: &f32| (x * 10.0).round() as i16)]
     pub speed_y: f32,
     /// meters / sec
     #[br(map = |x: i16| (x as f32 / 10.0))]
+    #[bw(map = |x: &f32| (x * 10.0).round() as i16)]
     pub speed_z: f32,
     /// degrees
     #[br(map = |x: i16| (x as f32 / 10.0))]
+    #[bw(map = |x: &f32| (x * 10.0).round() as i16)]
     pub pitch: f32,
     /// degrees
     #[br(map = |x: i16| (x as f32 / 10.0))]
+    #[bw(map = |x: &f32| (x * 10.0).round() as i16)]
     pub roll: f32,
     /// degrees
     #[br(map = |x: i16| (x as f32 / 10.0))]
+    #[bw(map = |x: &f32| (x * 10.0).round() as i16)]
     pub yaw: f32,
 
     #[br(temp)]
+    #[bw(calc = pack_byte_field(u8::from(*flight_mode), 0x7F) | pack_byte_field(*rc_outcontrol as u8, 0x80))]
     _bitpack1: u8,
     #[br(calc(FlightMode::from(sub_byte_field(_bitpack1, 0x7F))))]
+    #[bw(ignore)]
     pub flight_mode: FlightMode,
     #[br(calc(sub_byte_field(_bitpack1, 0x80) == 1))]
+    #[bw(ignore)]
     pub rc_outcontrol: bool,
 
     #[br(map = |x: u8| AppCommand::from(x))]
+    #[bw(map = |x: &AppCommand| u8::from(*x))]
     pub app_command: AppCommand,
 
     #[br(temp)]
+    #[bw(calc = pack_byte_field(*can_ioc_work as u8, 0x01)
+        | pack_byte_field(u8::from(*ground_or_sky), 0x06)
+        | pack_byte_field(*is_motor_up as u8, 0x08)
+        | pack_byte_field(*is_swave_work as u8, 0x10)
+        | pack_byte_field(u8::from(*go_home_status), 0xE0))]
     _bitpack2: u8,
     #[br(calc(sub_byte_field(_bitpack2, 0x01) == 1))]
+    #[bw(ignore)]
     pub can_ioc_work: bool,
     #[br(calc(GroundOrSky::from(sub_byte_field(_bitpack2, 0x06))))]
+    #[bw(ignore)]
     pub ground_or_sky: GroundOrSky,
     #[br(calc(sub_byte_field(_bitpack2, 0x08) == 1))]
+    #[bw(ignore)]
     pub is_motor_up: bool,
     #[br(calc(sub_byte_field(_bitpack2, 0x10) == 1))]
+    #[bw(ignore)]
     pub is_swave_work: bool,
     #[br(calc(GoHomeStatus::from(sub_byte_field(_bitpack2, 0xE0))))]
+    #[bw(ignore)]
     pub go_home_status: GoHomeStatus,
 
     #[br(temp)]
+    #[bw(calc = pack_byte_field(*is_vision_used as u8, 0x01)
+        | pack_byte_field(*voltage_warning, 0x06)
+        | pack_byte_field(*is_imu_preheated as u8, 0x10)
+        | pack_byte_field(*mode_channel, 0x60)
+        | pack_byte_field(*is_gps_valid as u8, 0x80))]
     _bitpack3: u8,
     #[br(calc(sub_byte_field(_bitpack3, 0x01) == 1))]
+    #[bw(ignore)]
     pub is_vision_used: bool,
     #[br(calc(sub_byte_field(_bitpack3, 0x06)))]
+    #[bw(ignore)]
     pub voltage_warning: u8,
     #[br(calc(sub_byte_field(_bitpack3, 0x10) == 1))]
+    #[bw(ignore)]
     pub is_imu_preheated: bool,
     #[br(calc(sub_byte_field(_bitpack3, 0x60)))]
+    #[bw(ignore)]
     pub mode_channel: u8,
     #[br(calc(sub_byte_field(_bitpack3, 0x80) == 1))]
+    #[bw(ignore)]
     pub is_gps_valid: bool,
 
     #[br(temp)]
+    #[bw(calc = pack_byte_field(*is_compass_error as u8, 0x01)
+        | pack_byte_field(*wave_error as u8, 0x02)
+        | pack_byte_field(*gps_level, 0x3C)
+        | pack_byte_field(u8::from(*battery_type), 0xC0))]
     _bitpack4: u8,
     #[br(calc(sub_byte_field(_bitpack4, 0x01) == 1))]
+    #[bw(ignore)]
     pub is_compass_error: bool,
     #[br(calc(sub_byte_field(_bitpack4, 0x02) == 1))]
+    #[bw(ignore)]
     pub wave_error: bool,
     #[br(calc(sub_byte_field(_bitpack4, 0x3C)))]
+    #[bw(ignore)]
     pub gps_level: u8,
     #[br(calc(BatteryType::from(sub_byte_field(_bitpack4, 0xC0))))]
+    #[bw(ignore)]
     pub battery_type: BatteryType,
 
     #[br(temp)]
+    #[bw(calc = pack_byte_field(*is_out_of_limit as u8, 0x01)
+        | pack_byte_field(*is_go_home_height_modified as u8, 0x02)
+        | pack_byte_field(*is_propeller_catapult as u8, 0x04)
+        | pack_byte_field(*is_motor_blocked as u8, 0x08)
+        | pack_byte_field(*is_not_enough_force as u8, 0x10)
+        | pack_byte_field(*is_barometer_dead_in_air as u8, 0x20)
+        | pack_byte_field(*is_vibrating as u8, 0x40)
+        | pack_byte_field(*is_acceletor_over_range as u8, 0x80))]
     _bitpack5: u8,
     #[br(calc(sub_byte_field(_bitpack5, 0x01) == 1))]
+    #[bw(ignore)]
     pub is_out_of_limit: bool,
     #[br(calc(sub_byte_field(_bitpack5, 0x02) == 1))]
+    #[bw(ignore)]
     pub is_go_home_height_modified: bool,
     #[br(calc(sub_byte_field(_bitpack5, 0x04) == 1))]
+    #[bw(ignore)]
     pub is_propeller_catapult: bool,
     #[br(calc(sub_byte_field(_bitpack5, 0x08) == 1))]
+    #[bw(ignore)]
     pub is_motor_blocked: bool,
     #[br(calc(sub_byte_field(_bitpack5, 0x10) == 1))]
+    #[bw(ignore)]
     pub is_not_enough_force: bool,
     #[br(calc(sub_byte_field(_bitpack5, 0x20) == 1))]
+    #[bw(ignore)]
     pub is_barometer_dead_in_air: bool,
     #[br(calc(sub_byte_field(_bitpack5, 0x40) == 1))]
+    #[bw(ignore)]
     pub is_vibrating: bool,
     #[br(calc(sub_byte_field(_bitpack5, 0x80) == 1))]
+    #[bw(ignore)]
     pub is_acceletor_over_range: bool,
 
     pub gps_num: u8,
     #[br(map = |x: u8| FlightAction::from(x))]
+    #[bw(map = |x: &FlightAction| u8::from(*x))]
     pub flight_action: FlightAction,
     #[br(map = |x: u8| MotorStartFailedCause::from(x))]
+    #[bw(map = |x: &MotorStartFailedCause| u8::from(*x))]
     pub motor_start_failed_cause: MotorStartFailedCause,
 
     #[br(temp)]
+    #[bw(calc = pack_byte_field(u8::from(*non_gps_cause), 0x0F) | pack_byte_field(*waypoint_limit_mode as u8, 0x10))]
     _bitpack6: u8,
     #[br(calc(NonGPSCause::from(sub_byte_field(_bitpack6, 0x0F))))]
+    #[bw(ignore)]
     pub non_gps_cause: NonGPSCause,
     #[br(calc(sub_byte_field(_bitpack6, 0x10) == 1))]
+    #[bw(ignore)]
     pub waypoint_limit_mode: bool,
 
     pub battery: u8,
     /// meters
     #[br(map = |x: u8| (x as f32 / 10.0))]
+    #[bw(map = |x: &f32| (x * 10.0).round() as u8)]
     pub s_wave_height: f32,
     /// second
     #[br(map = |x: u16| (x as f32 / 10.0))]
+    #[bw(map = |x: &f32| (x * 10.0).round() as u16)]
     pub fly_time: f32,
     pub motor_revolution: u8,
     #[br(temp)]
+    #[bw(calc = 0)]
     _unknown: u16,
     pub version_c: u8,
     #[br(if(version >=2), map = |x: u8| DroneType::from(x))]
+    #[bw(if(version >=2), map = |x: &DroneType| u8::from(*x))]
     pub drone_type: DroneType,
     #[br(if(version >=3), map = |x: u8| ImuInitFailReason::from(x))]
+    #[bw(if(version >=3), map = |x: &ImuInitFailReason| u8::from(*x))]
     pub imu_init_fail_reason: ImuInitFailReason,
 }
 
@@ -226,10 +293,56 @@ impl From<u8> for DroneType {
     }
 }
 
-#[derive(Serialize, Debug, Copy, Clone, PartialEq, Eq)]
+impl From<DroneType> for u8 {
+    fn from(value: DroneType) -> Self {
+        match value {
+            DroneType::None => 0,
+            DroneType::Inspire1 => 1,
+            DroneType::Phantom3Advanced => 2,
+            DroneType::Phantom3Pro => 3,
+            DroneType::Phantom3Standard => 4,
+            DroneType::OpenFrame => 5,
+            DroneType::AceOne => 6,
+            DroneType::WKM => 7,
+            DroneType::Naza => 8,
+            DroneType::A2 => 9,
+            DroneType::A3 => 10,
+            DroneType::Phantom4 => 11,
+            DroneType::Matrice600 => 14,
+            DroneType::Phantom34K => 15,
+            DroneType::MavicPro => 16,
+            DroneType::Inspire2 => 17,
+            DroneType::Phantom4Pro => 18,
+            DroneType::N3 => 20,
+            DroneType::Spark => 21,
+            DroneType::Matrice600Pro => 23,
+            DroneType::MavicAir => 24,
+            DroneType::Matrice200 => 25,
+            DroneType::Phantom4Advanced => 27,
+            DroneType::Matrice210 => 28,
+            DroneType::Phantom3SE => 29,
+            DroneType::Matrice210RTK => 30,
+            DroneType::Phantom4ProV2 => 36,
+            DroneType::Mavic2 => 41,
+            DroneType::Mavic2Enterprise => 51,
+            DroneType::MavicAir2 => 58,
+            DroneType::Matrice300RTK => 60,
+            DroneType::Mini2 => 63,
+            DroneType::Mavic3Enterprise => 77,
+            DroneType::Mavic3Pro => 84,
+            DroneType::Matrice350RTK => 89,
+            DroneType::Mini4Pro => 93,
+            DroneType::Avata2 => 94,
+            DroneType::Unknown(value) => value,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Copy, Clone, PartialEq, Eq, Default)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub enum FlightMode {
     /// Manual mode. Shown as Manual in DJI app.
+    #[default]
     Manual,
     /// Attitude mode. Shown as Atti in DJI app.
     Atti,
@@ -345,9 +458,56 @@ impl From<u8> for FlightMode {
     }
 }
 
-#[derive(Serialize, Debug, Clone, Copy)]
+impl From<FlightMode> for u8 {
+    fn from(value: FlightMode) -> Self {
+        match value {
+            FlightMode::Manual => 0,
+            FlightMode::Atti => 1,
+            FlightMode::AttiCourseLock => 2,
+            FlightMode::AttiHover => 3,
+            FlightMode::Hover => 4,
+            FlightMode::GPSBlake => 5,
+            FlightMode::GPSAtti => 6,
+            FlightMode::GPSCourseLock => 7,
+            FlightMode::GPSHomeLock => 8,
+            FlightMode::GPSHotPoint => 9,
+            FlightMode::AssistedTakeoff => 10,
+            FlightMode::AutoTakeoff => 11,
+            FlightMode::AutoLanding => 12,
+            FlightMode::AttiLanding => 13,
+            FlightMode::GPSWaypoint => 14,
+            FlightMode::GoHome => 15,
+            FlightMode::ClickGo => 16,
+            FlightMode::Joystick => 17,
+            FlightMode::GPSAttiWristband => 18,
+            FlightMode::Cinematic => 19,
+            FlightMode::AttiLimited => 23,
+            FlightMode::Draw => 24,
+            FlightMode::GPSFollowMe => 25,
+            FlightMode::ActiveTrack => 26,
+            FlightMode::TapFly => 27,
+            FlightMode::Pano => 28,
+            FlightMode::Farming => 29,
+            FlightMode::FPV => 30,
+            FlightMode::GPSSport => 31,
+            FlightMode::GPSNovice => 32,
+            FlightMode::ConfirmLanding => 33,
+            FlightMode::TerrainTracking => 35,
+            FlightMode::NaviAdvGoHome => 36,
+            FlightMode::NaviAdvLanding => 37,
+            FlightMode::Tripod => 38,
+            FlightMode::TrackHeadlock => 39,
+            FlightMode::EngineStart => 41,
+            FlightMode::GPSGentle => 43,
+            FlightMode::Unknown(value) => value,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Copy, Default)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub enum AppCommand {
+    #[default]
     AutoFly,
     AutoLanding,
     HomePointNow,
@@ -418,9 +578,47 @@ impl From<u8> for AppCommand {
     }
 }
 
-#[derive(PartialEq, Serialize, Debug, Clone, Copy)]
+impl From<AppCommand> for u8 {
+    fn from(value: AppCommand) -> Self {
+        match value {
+            AppCommand::AutoFly => 1,
+            AppCommand::AutoLanding => 2,
+            AppCommand::HomePointNow => 3,
+            AppCommand::HomePointHot => 4,
+            AppCommand::HomePointLock => 5,
+            AppCommand::GoHome => 6,
+            AppCommand::StartMotor => 7,
+            AppCommand::StopMotor => 8,
+            AppCommand::Calibration => 9,
+            AppCommand::DeformProtecClose => 10,
+            AppCommand::DeformProtecOpen => 11,
+            AppCommand::DropGoHome => 12,
+            AppCommand::DropTakeOff => 13,
+            AppCommand::DropLanding => 14,
+            AppCommand::DynamicHomePointOpen => 15,
+            AppCommand::DynamicHomePointClose => 16,
+            AppCommand::FollowFunctionOpen => 17,
+            AppCommand::FollowFunctionClose => 18,
+            AppCommand::IOCOpen => 19,
+            AppCommand::IOCClose => 20,
+            AppCommand::DropCalibration => 21,
+            AppCommand::PackMode => 22,
+            AppCommand::UnPackMode => 23,
+            AppCommand::EnterManualMode => 24,
+            AppCommand::StopDeform => 25,
+            AppCommand::DownDeform => 28,
+            AppCommand::UpDeform => 29,
+            AppCommand::ForceLanding => 30,
+            AppCommand::ForceLanding2 => 31,
+            AppCommand::Unknown(value) => value,
+        }
+    }
+}
+
+#[derive(PartialEq, Serialize, Debug, Clone, Copy, Default)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub enum GroundOrSky {
+    #[default]
     Ground,
     Sky,
     #[serde(untagged)]
@@ -437,9 +635,20 @@ impl From<u8> for GroundOrSky {
     }
 }
 
-#[derive(Serialize, Debug, Clone, Copy)]
+impl From<GroundOrSky> for u8 {
+    fn from(value: GroundOrSky) -> Self {
+        match value {
+            GroundOrSky::Ground => 0,
+            GroundOrSky::Sky => 2,
+            GroundOrSky::Unknown(value) => value,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Copy, Default)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub enum GoHomeStatus {
+    #[default]
     Standby,
     Preascending,
     Align,
@@ -466,9 +675,25 @@ impl From<u8> for GoHomeStatus {
     }
 }
 
-#[derive(Serialize, Debug, Clone, Copy)]
+impl From<GoHomeStatus> for u8 {
+    fn from(value: GoHomeStatus) -> Self {
+        match value {
+            GoHomeStatus::Standby => 0,
+            GoHomeStatus::Preascending => 1,
+            GoHomeStatus::Align => 2,
+            GoHomeStatus::Ascending => 3,
+            GoHomeStatus::Cruise => 4,
+            GoHomeStatus::Braking => 5,
+            GoHomeStatus::Bypassing => 6,
+            GoHomeStatus::Unknown(value) => value,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Copy, Default)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub enum BatteryType {
+    #[default]
     NonSmart,
     Smart,
     #[serde(untagged)]
@@ -485,9 +710,20 @@ impl From<u8> for BatteryType {
     }
 }
 
-#[derive(Serialize, Debug, Clone, Copy)]
+impl From<BatteryType> for u8 {
+    fn from(value: BatteryType) -> Self {
+        match value {
+            BatteryType::NonSmart => 1,
+            BatteryType::Smart => 2,
+            BatteryType::Unknown(value) => value,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Copy, Default)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub enum FlightAction {
+    #[default]
     None,
     WarningPowerGoHome,
     WarningPowerLanding,
@@ -570,9 +806,53 @@ impl From<u8> for FlightAction {
     }
 }
 
-#[derive(Serialize, Debug, Clone, Copy)]
+impl From<FlightAction> for u8 {
+    fn from(value: FlightAction) -> Self {
+        match value {
+            FlightAction::None => 0,
+            FlightAction::WarningPowerGoHome => 1,
+            FlightAction::WarningPowerLanding => 2,
+            FlightAction::SmartPowerGoHome => 3,
+            FlightAction::SmartPowerLanding => 4,
+            FlightAction::LowVoltageLanding => 5,
+            FlightAction::LowVoltageGoHome => 6,
+            FlightAction::SeriousLowVoltageLanding => 7,
+            FlightAction::RCOnekeyGoHome => 8,
+            FlightAction::RCAssistantTakeoff => 9,
+            FlightAction::RCAutoTakeoff => 10,
+            FlightAction::RCAutoLanding => 11,
+            FlightAction::AppAutoGoHome => 12,
+            FlightAction::AppAutoLanding => 13,
+            FlightAction::AppAutoTakeoff => 14,
+            FlightAction::OutOfControlGoHome => 15,
+            FlightAction::ApiAutoTakeoff => 16,
+            FlightAction::ApiAutoLanding => 17,
+            FlightAction::ApiAutoGoHome => 18,
+            FlightAction::AvoidGroundLanding => 19,
+            FlightAction::AirportAvoidLanding => 20,
+            FlightAction::TooCloseGoHomeLanding => 21,
+            FlightAction::TooFarGoHomeLanding => 22,
+            FlightAction::AppWPMission => 23,
+            FlightAction::WPAutoTakeoff => 24,
+            FlightAction::GoHomeAvoid => 25,
+            FlightAction::PGoHomeFinish => 26,
+            FlightAction::VertLowLimitLanding => 27,
+            FlightAction::BatteryForceLanding => 28,
+            FlightAction::MCProtectGoHome => 29,
+            FlightAction::MotorblockLanding => 30,
+            FlightAction::AppRequestForceLanding => 31,
+            FlightAction::FakeBatteryLanding => 32,
+            FlightAction::RTHComingObstacleLanding => 33,
+            FlightAction::IMUErrorRTH => 34,
+            FlightAction::Unknown(value) => value,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Copy, Default)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub enum MotorStartFailedCause {
+    #[default]
     None,
     CompassError,
     AssistantProtected,
@@ -770,9 +1050,110 @@ impl From<u8> for MotorStartFailedCause {
     }
 }
 
-#[derive(Serialize, Debug, Clone, Copy)]
+impl From<MotorStartFailedCause> for u8 {
+    fn from(value: MotorStartFailedCause) -> Self {
+        match value {
+            MotorStartFailedCause::None => 0,
+            MotorStartFailedCause::CompassError => 1,
+            MotorStartFailedCause::AssistantProtected => 2,
+            MotorStartFailedCause::DeviceLocked => 3,
+            MotorStartFailedCause::DistanceLimit => 4,
+            MotorStartFailedCause::IMUNeedCalibration => 5,
+            MotorStartFailedCause::IMUSNError => 6,
+            MotorStartFailedCause::IMUWarning => 7,
+            MotorStartFailedCause::CompassCalibrating => 8,
+            MotorStartFailedCause::AttiError => 9,
+            MotorStartFailedCause::NoviceProtected => 10,
+            MotorStartFailedCause::BatteryCellError => 11,
+            MotorStartFailedCause::BatteryCommuniteError => 12,
+            MotorStartFailedCause::SeriousLowVoltage => 13,
+            MotorStartFailedCause::SeriousLowPower => 14,
+            MotorStartFailedCause::LowVoltage => 15,
+            MotorStartFailedCause::TempureVolLow => 16,
+            MotorStartFailedCause::SmartLowToLand => 17,
+            MotorStartFailedCause::BatteryNotReady => 18,
+            MotorStartFailedCause::SimulatorMode => 19,
+            MotorStartFailedCause::PackMode => 20,
+            MotorStartFailedCause::AttitudeAbnormal => 21,
+            MotorStartFailedCause::UnActive => 22,
+            MotorStartFailedCause::FlyForbiddenError => 23,
+            MotorStartFailedCause::BiasError => 24,
+            MotorStartFailedCause::EscError => 25,
+            MotorStartFailedCause::ImuInitError => 26,
+            MotorStartFailedCause::SystemUpgrade => 27,
+            MotorStartFailedCause::SimulatorStarted => 28,
+            MotorStartFailedCause::ImuingError => 29,
+            MotorStartFailedCause::AttiAngleOver => 30,
+            MotorStartFailedCause::GyroscopeError => 31,
+            MotorStartFailedCause::AcceleratorError => 32,
+            MotorStartFailedCause::CompassFailed => 33,
+            MotorStartFailedCause::BarometerError => 34,
+            MotorStartFailedCause::BarometerNegative => 35,
+            MotorStartFailedCause::CompassBig => 36,
+            MotorStartFailedCause::GyroscopeBiasBig => 37,
+            MotorStartFailedCause::AcceleratorBiasBig => 38,
+            MotorStartFailedCause::CompassNoiseBig => 39,
+            MotorStartFailedCause::BarometerNoiseBig => 40,
+            MotorStartFailedCause::InvalidSn => 41,
+            MotorStartFailedCause::FlashOperating => 44,
+            MotorStartFailedCause::GPSdisconnect => 45,
+            MotorStartFailedCause::SDCardException => 47,
+            MotorStartFailedCause::IMUNoconnection => 61,
+            MotorStartFailedCause::RCCalibration => 62,
+            MotorStartFailedCause::RCCalibrationException => 63,
+            MotorStartFailedCause::RCCalibrationUnfinished => 64,
+            MotorStartFailedCause::RCCalibrationException2 => 65,
+            MotorStartFailedCause::RCCalibrationException3 => 66,
+            MotorStartFailedCause::AircraftTypeMismatch => 67,
+            MotorStartFailedCause::FoundUnfinishedModule => 68,
+            MotorStartFailedCause::CyroAbnormal => 70,
+            MotorStartFailedCause::BaroAbnormal => 71,
+            MotorStartFailedCause::CompassAbnormal => 72,
+            MotorStartFailedCause::GPSAbnormal => 73,
+            MotorStartFailedCause::NSAbnormal => 74,
+            MotorStartFailedCause::TopologyAbnormal => 75,
+            MotorStartFailedCause::RCNeedCali => 76,
+            MotorStartFailedCause::InvalidFloat => 77,
+            MotorStartFailedCause::M600BatTooLittle => 78,
+            MotorStartFailedCause::M600BatAuthErr => 79,
+            MotorStartFailedCause::M600BatCommErr => 80,
+            MotorStartFailedCause::M600BatDifVoltLarge1 => 81,
+            MotorStartFailedCause::M600BatDifVoltLarge2 => 82,
+            MotorStartFailedCause::InvalidVersion => 83,
+            MotorStartFailedCause::GimbalGyroAbnormal => 84,
+            MotorStartFailedCause::GimbalESCPitchNonData => 85,
+            MotorStartFailedCause::GimbalESCRollNonData => 86,
+            MotorStartFailedCause::GimbalESCYawNonData => 87,
+            MotorStartFailedCause::GimbalFirmwIsUpdating => 88,
+            MotorStartFailedCause::GimbalDisorder => 89,
+            MotorStartFailedCause::GimbalPitchShock => 90,
+            MotorStartFailedCause::GimbalRollShock => 91,
+            MotorStartFailedCause::GimbalYawShock => 92,
+            MotorStartFailedCause::IMUcCalibrationFinished => 93,
+            MotorStartFailedCause::BattVersionError => 101,
+            MotorStartFailedCause::RTKBadSignal => 102,
+            MotorStartFailedCause::RTKDeviationError => 103,
+            MotorStartFailedCause::ESCCalibrating => 112,
+            MotorStartFailedCause::GPSSignInvalid => 113,
+            MotorStartFailedCause::GimbalIsCalibrating => 114,
+            MotorStartFailedCause::LockByApp => 115,
+            MotorStartFailedCause::StartFlyHeightError => 116,
+            MotorStartFailedCause::ESCVersionNotMatch => 117,
+            MotorStartFailedCause::IMUOriNotMatch => 118,
+            MotorStartFailedCause::StopByApp => 119,
+            MotorStartFailedCause::CompassIMUOriNotMatch => 120,
+            MotorStartFailedCause::BatteryOverTemperature => 123,
+            MotorStartFailedCause::BatteryInstallError => 124,
+            MotorStartFailedCause::BeImpact => 125,
+            MotorStartFailedCause::Unknown(value) => value,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Copy, Default)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub enum NonGPSCause {
+    #[default]
     Already,
     Forbid,
     GpsNumNonEnough,
@@ -801,6 +1182,22 @@ impl From<u8> for NonGPSCause {
     }
 }
 
+impl From<NonGPSCause> for u8 {
+    fn from(value: NonGPSCause) -> Self {
+        match value {
+            NonGPSCause::Already => 0,
+            NonGPSCause::Forbid => 1,
+            NonGPSCause::GpsNumNonEnough => 2,
+            NonGPSCause::GpsHdopLarge => 3,
+            NonGPSCause::GpsPositionNonMatch => 4,
+            NonGPSCause::SpeedErrorLarge => 5,
+            NonGPSCause::YawErrorLarge => 6,
+            NonGPSCause::CompassErrorLarge => 7,
+            NonGPSCause::Unknown(value) => value,
+        }
+    }
+}
+
 #[derive(Serialize, Debug, Default, Clone, Copy)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub enum ImuInitFailReason {
@@ -846,3 +1243,26 @@ impl From<u8> for ImuInitFailReason {
         }
     }
 }
+
+impl From<ImuInitFailReason> for u8 {
+    fn from(value: ImuInitFailReason) -> Self {
+        match value {
+            ImuInitFailReason::MonitorError => 0,
+            ImuInitFailReason::CollectingData => 1,
+            ImuInitFailReason::AcceDead => 3,
+            ImuInitFailReason::CompassDead => 4,
+            ImuInitFailReason::BarometerDead => 5,
+            ImuInitFailReason::BarometerNegative => 6,
+            ImuInitFailReason::CompassModTooLarge => 7,
+            ImuInitFailReason::GyroBiasTooLarge => 8,
+            ImuInitFailReason::AcceBiasTooLarge => 9,
+            ImuInitFailReason::CompassNoiseTooLarge => 10,
+            ImuInitFailReason::BarometerNoiseTooLarge => 11,
+            ImuInitFailReason::WaitingMcStationary => 12,
+            ImuInitFailReason::AcceMoveTooLarge => 13,
+            ImuInitFailReason::McHeaderMoved => 14,
+            ImuInitFailReason::McVibrated => 15,
+            ImuInitFailReason::Unknown(value) => value,
+        }
+    }
+}
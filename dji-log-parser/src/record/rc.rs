@@ -1,15 +1,15 @@
-use binrw::binread;
+use binrw::binrw;
 use serde::Serialize;
 #[cfg(target_arch = "wasm32")]
 use tsify_next::Tsify;
 
 use crate::layout::details::ProductType;
-use crate::utils::sub_byte_field;
+use crate::utils::{pack_byte_field, sub_byte_field};
 
-#[binread]
-#[derive(Serialize, Debug)]
+#[binrw]
+#[derive(Serialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
-#[br(little, import { version: u8, product_type: ProductType = ProductType::None })]
+#[brw(little, import { version: u8, product_type: ProductType = ProductType::None })]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub struct RC {
     /// right stick - horizontal
@@ -23,42 +23,72 @@ pub struct RC {
     pub gimbal: u16,
 
     #[br(temp)]
+    #[bw(calc = pack_byte_field(*wheel_btn_down as u8, 0x01)
+        | pack_byte_field(*wheel_offset, 0x3E)
+        | pack_byte_field(*wheel_polarity, 0x40)
+        | pack_byte_field(*wheel_change, 0x80))]
     _bitpack1: u8,
     #[br(calc(sub_byte_field(_bitpack1, 0x01) == 1))]
+    #[bw(ignore)]
     pub wheel_btn_down: bool,
     #[br(calc(sub_byte_field(_bitpack1, 0x3E)))]
+    #[bw(ignore)]
     pub wheel_offset: u8,
     #[br(calc(sub_byte_field(_bitpack1, 0x40)))]
+    #[bw(ignore)]
     pub wheel_polarity: u8,
     #[br(calc(sub_byte_field(_bitpack1, 0x80)))]
+    #[bw(ignore)]
     pub wheel_change: u8,
 
     #[br(temp)]
+    #[bw(calc = pack_byte_field(*transform_btn_reserve, 0x07)
+        | pack_byte_field(*return_btn as u8, 0x08)
+        | pack_byte_field(flight_mode_switch.to_u8(product_type), 0x30)
+        | pack_byte_field(*transform_switch, 0xC0))]
     _bitpack2: u8,
     #[br(calc(sub_byte_field(_bitpack2, 0x07)))]
+    #[bw(ignore)]
     pub transform_btn_reserve: u8,
     #[br(calc(sub_byte_field(_bitpack2, 0x08) == 1))]
+    #[bw(ignore)]
     pub return_btn: bool,
     #[br(calc(FlightModeSwitch::from(sub_byte_field(_bitpack2, 0x30), product_type)))]
+    #[bw(ignore)]
     pub flight_mode_switch: FlightModeSwitch,
     #[br(calc(sub_byte_field(_bitpack2, 0xC0)))]
+    #[bw(ignore)]
     pub transform_switch: u8,
 
     #[br(temp)]
+    #[bw(calc = pack_byte_field(*custom_function_btn4_down as u8, 0x02)
+        | pack_byte_field(*custom_function_btn3_down as u8, 0x04)
+        | pack_byte_field(*custom_function_btn2_down as u8, 0x08)
+        | pack_byte_field(*custom_function_btn1_down as u8, 0x10)
+        | pack_byte_field(*playback_btn_down as u8, 0x20)
+        | pack_byte_field(*shutter_btn_down as u8, 0x40)
+        | pack_byte_field(*record_btn_down as u8, 0x80))]
     _bitpack3: u8,
     #[br(calc(sub_byte_field(_bitpack3, 0x02) == 1))]
+    #[bw(ignore)]
     pub custom_function_btn4_down: bool,
     #[br(calc(sub_byte_field(_bitpack3, 0x04) == 1))]
+    #[bw(ignore)]
     pub custom_function_btn3_down: bool,
     #[br(calc(sub_byte_field(_bitpack3, 0x08) == 1))]
+    #[bw(ignore)]
     pub custom_function_btn2_down: bool,
     #[br(calc(sub_byte_field(_bitpack3, 0x10) == 1))]
+    #[bw(ignore)]
     pub custom_function_btn1_down: bool,
     #[br(calc(sub_byte_field(_bitpack3, 0x20) == 1))]
+    #[bw(ignore)]
     pub playback_btn_down: bool,
     #[br(calc(sub_byte_field(_bitpack3, 0x40) == 1))]
+    #[bw(ignore)]
     pub shutter_btn_down: bool,
     #[br(calc(sub_byte_field(_bitpack3, 0x80) == 1))]
+    #[bw(ignore)]
     pub record_btn_down: bool,
 
     #[br(if(version >= 6))]
@@ -67,7 +97,7 @@ pub struct RC {
     pub gimbal_control_enable: u8,
 }
 
-#[derive(Serialize, Debug, Clone, Copy)]
+#[derive(Serialize, Debug, Clone, Copy, Default)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub enum FlightModeSwitch {
     /// Position One. For all products except Mavic Pro, this is the left most position
@@ -75,6 +105,7 @@ pub enum FlightModeSwitch {
     /// pilot. For example, on a Phantom 4 remote controller,  Position One is labeled
     /// "A". For Mavic Pro, Spark and Mavic Air, this is  the position that is furthest
     /// away from the pilot and labeled "Sport".
+    #[default]
     One,
     /// Position Two. For all products except Mavic Pro, this is the middle position of
     /// the flight mode switch on a remote controller from the perspective of the pilot.
@@ -112,4 +143,24 @@ impl FlightModeSwitch {
             _ => FlightModeSwitch::Unknown(mapped_value),
         }
     }
+
+    pub fn to_u8(self, product_type: ProductType) -> u8 {
+        let mapped_value = match self {
+            FlightModeSwitch::One => 0,
+            FlightModeSwitch::Two => 1,
+            FlightModeSwitch::Three => 2,
+            FlightModeSwitch::Unknown(value) => value,
+        };
+
+        match product_type {
+            // Remap values for Mavic Pro
+            ProductType::MavicPro => match mapped_value {
+                2 => 0,
+                3 => 1,
+                1 => 2,
+                _ => mapped_value,
+            },
+            _ => mapped_value,
+        }
+    }
 }
@@ -1,15 +1,16 @@
-use binrw::binread;
+use binrw::binrw;
 use serde::Serialize;
 #[cfg(target_arch = "wasm32")]
 use tsify_next::Tsify;
 
-#[binread]
-#[derive(Serialize, Debug)]
+#[binrw]
+#[derive(Serialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
-#[br(little)]
+#[brw(little)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub struct RCDisplayField {
     #[br(temp)]
+    #[bw(calc = [0; 7])]
     _unknown: [u8; 7],
     /// right stick - horizontal
     pub aileron: u16,
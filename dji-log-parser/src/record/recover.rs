@@ -1,37 +1,48 @@
-use binrw::binread;
+use binrw::binrw;
 use chrono::{DateTime, Utc};
 use serde::Serialize;
 #[cfg(target_arch = "wasm32")]
 use tsify_next::Tsify;
 
-use crate::layout::details::{Platform, ProductType, parse_battery_sn};
+use crate::layout::details::{encode_battery_sn, parse_battery_sn, Platform, ProductType};
+use crate::utils::{string_to_bytes, version_to_bytes};
 
-#[binread]
-#[derive(Serialize, Debug)]
+#[binrw]
+#[derive(Serialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
-#[br(little, import {version: u8})]
+#[brw(little, import {version: u8})]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub struct Recover {
     #[br(map = |x: u8| ProductType::from(x))]
+    #[bw(map = |x: &ProductType| u8::from(*x))]
     pub product_type: ProductType,
     #[br(map = |x: u8| Platform::from(x))]
+    #[bw(map = |x: &Platform| u8::from(*x))]
     pub app_platform: Platform,
     #[br(map = |x: [u8; 3]| format!("{}.{}.{}", x[0], x[1], x[2]))]
+    #[bw(map = |x: &String| version_to_bytes(x, 3))]
     pub app_version: String,
     #[br(count = if version <= 7 { 10 } else { 16 }, map = |s: Vec<u8>| String::from_utf8_lossy(&s).trim_end_matches('\0').to_string())]
+    #[bw(map = |s: &String| string_to_bytes(s, if version <= 7 { 10 } else { 16 }))]
     pub aircraft_sn: String,
     #[br(count = 32, map = |s: Vec<u8>| String::from_utf8_lossy(&s).trim_end_matches('\0').to_string())]
+    #[bw(map = |s: &String| string_to_bytes(s, 32))]
     pub aircraft_name: String,
     #[br(map = |x: i64| DateTime::from_timestamp(x, 0).unwrap_or_default())]
+    #[bw(map = |x: &DateTime<Utc>| x.timestamp())]
     #[cfg_attr(target_arch = "wasm32", tsify(type = "string"))]
     pub timestamp: DateTime<Utc>,
     #[br(count = if version <= 7 { 10 } else { 16 }, map = |s: Vec<u8>| String::from_utf8_lossy(&s).trim_end_matches('\0').to_string())]
+    #[bw(map = |s: &String| string_to_bytes(s, if version <= 7 { 10 } else { 16 }))]
     pub camera_sn: String,
     #[br(count = if version <= 7 { 10 } else { 16 }, map = |s: Vec<u8>| String::from_utf8_lossy(&s).trim_end_matches('\0').to_string())]
+    #[bw(map = |s: &String| string_to_bytes(s, if version <= 7 { 10 } else { 16 }))]
     pub rc_sn: String,
     #[br(count = if version <= 7 { 10 } else { 16 })]
     #[br(temp)]
+    #[bw(calc = encode_battery_sn(ProductType::from(product_type), battery_sn, if version <= 7 { 10 } else { 16 }))]
     battery_buf: Vec<u8>,
     #[br(calc = parse_battery_sn(product_type, battery_buf))]
+    #[bw(ignore)]
     pub battery_sn: String,
 }
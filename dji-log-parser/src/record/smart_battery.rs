@@ -1,14 +1,14 @@
-use binrw::binread;
+use binrw::binrw;
 use serde::Serialize;
 #[cfg(target_arch = "wasm32")]
 use tsify_next::Tsify;
 
-use crate::utils::sub_byte_field;
+use crate::utils::{pack_byte_field, sub_byte_field};
 
-#[binread]
-#[derive(Serialize, Debug)]
+#[binrw]
+#[derive(Serialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
-#[br(little)]
+#[brw(little)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub struct SmartBattery {
     pub useful_time: u16,
@@ -20,33 +20,42 @@ pub struct SmartBattery {
     pub volume_consume: f32,
     pub status: u32,
     #[br(map = |x: u8| BatteryGoHomeStatus::from(x))]
+    #[bw(map = |x: &BatteryGoHomeStatus| u8::from(*x))]
     pub go_home_status: BatteryGoHomeStatus,
     pub go_home_countdown: u8,
     /// volts
     #[br(map = |x: u16| x as f32 / 1000.0)]
+    #[bw(map = |x: &f32| (x * 1000.0).round() as u16)]
     pub voltage: f32,
     pub percent: u8,
 
     #[br(temp)]
+    #[bw(calc = pack_byte_field(*low_warning, 0x7F) | pack_byte_field(*low_warning_go_home, 0x80))]
     _bitpack1: u8,
     #[br(calc(sub_byte_field(_bitpack1, 0x7F)))]
+    #[bw(ignore)]
     pub low_warning: u8,
     #[br(calc(sub_byte_field(_bitpack1, 0x80)))]
+    #[bw(ignore)]
     pub low_warning_go_home: u8,
 
     #[br(temp)]
+    #[bw(calc = pack_byte_field(*serious_low_warning, 0x7F) | pack_byte_field(*serious_low_warning_landing, 0x80))]
     _bitpack2: u8,
     #[br(calc(sub_byte_field(_bitpack2, 0x7F)))]
+    #[bw(ignore)]
     pub serious_low_warning: u8,
     #[br(calc(sub_byte_field(_bitpack2, 0x80)))]
+    #[bw(ignore)]
     pub serious_low_warning_landing: u8,
 
     pub reserve: u8,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, Clone, Copy, Default)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub enum BatteryGoHomeStatus {
+    #[default]
     NonGoHome,
     GoHome,
     GoHomeAlready,
@@ -64,3 +73,14 @@ impl From<u8> for BatteryGoHomeStatus {
         }
     }
 }
+
+impl From<BatteryGoHomeStatus> for u8 {
+    fn from(value: BatteryGoHomeStatus) -> Self {
+        match value {
+            BatteryGoHomeStatus::NonGoHome => 0,
+            BatteryGoHomeStatus::GoHome => 1,
+            BatteryGoHomeStatus::GoHomeAlready => 2,
+            BatteryGoHomeStatus::Unknown(value) => value,
+        }
+    }
+}
@@ -1,25 +1,25 @@
-use binrw::binread;
+use binrw::binrw;
 use serde::Serialize;
 #[cfg(target_arch = "wasm32")]
 use tsify_next::Tsify;
 
-#[binread]
+#[binrw]
 #[derive(Serialize, Debug)]
 #[serde(tag = "type")]
-#[br(little)]
+#[brw(little)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub enum SmartBatteryGroup {
-    #[br(magic = 1u8)]
+    #[brw(magic = 1u8)]
     SmartBatteryStatic(SmartBatteryStatic),
-    #[br(magic = 2u8)]
+    #[brw(magic = 2u8)]
     SmartBatteryDynamic(SmartBatteryDynamic),
-    #[br(magic = 3u8)]
+    #[brw(magic = 3u8)]
     SmartBatterySingleVoltage(SmartBatterySingleVoltage),
 }
 
-#[binread]
-#[derive(Serialize, Debug)]
-#[br(little)]
+#[binrw]
+#[derive(Serialize, Debug, Default)]
+#[brw(little)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub struct SmartBatteryStatic {
     pub index: u8,
@@ -27,28 +27,33 @@ pub struct SmartBatteryStatic {
     pub loop_times: u16,
     pub full_voltage: u32,
     #[br(temp)]
+    #[bw(calc = 0)]
     _unknown: u16,
     serial_number: u16,
     #[br(temp)]
+    #[bw(calc = [0; 10])]
     _unknown2: [u8; 10],
     #[br(temp)]
+    #[bw(calc = [0; 5])]
     _unknown3: [u8; 5],
     pub version_number: [u8; 8],
     pub battery_life: u8,
     pub battery_type: u8,
 }
 
-#[binread]
-#[derive(Serialize, Debug)]
-#[br(little)]
+#[binrw]
+#[derive(Serialize, Debug, Default)]
+#[brw(little)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub struct SmartBatteryDynamic {
     pub index: u8,
     /// volts
     #[br(map = |x: i32| x as f32 / 1000.0)]
+    #[bw(map = |x: &f32| (x * 1000.0).round() as i32)]
     pub current_voltage: f32,
     // ampere
     #[br(map = |x: i32| x.abs() as f32 / 1000.0)]
+    #[bw(map = |x: &f32| (x * 1000.0).round() as i32)]
     pub current_current: f32,
     /// mAh
     pub full_capacity: u32,
@@ -56,19 +61,21 @@ pub struct SmartBatteryDynamic {
     pub remained_capacity: u32,
     /// degrees
     #[br(map = |x: i16| x as f32 / 10.0)]
+    #[bw(map = |x: &f32| (x * 10.0).round() as i16)]
     pub temperature: f32,
     pub cell_count: u8,
     pub capacity_percent: u8,
     pub battery_state: u64,
 }
 
-#[binread]
-#[derive(Serialize, Debug)]
-#[br(little)]
+#[binrw]
+#[derive(Serialize, Debug, Default)]
+#[brw(little)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub struct SmartBatterySingleVoltage {
     pub index: u8,
     pub cell_count: u8,
     #[br(count = cell_count, map = |xs: Vec<u16>| xs.into_iter().map(|x| x as f32 / 1000.0).collect())]
+    #[bw(map = |xs: &Vec<f32>| xs.iter().map(|x| (x * 1000.0).round() as u16).collect::<Vec<u16>>())]
     pub cell_voltages: Vec<f32>,
 }
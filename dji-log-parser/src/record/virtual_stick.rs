@@ -1,25 +1,33 @@
-use binrw::binread;
+use binrw::binrw;
 use serde::Serialize;
 #[cfg(target_arch = "wasm32")]
 use tsify_next::Tsify;
 
-use crate::utils::sub_byte_field;
+use crate::utils::{pack_byte_field, sub_byte_field};
 
-#[binread]
-#[derive(Serialize, Debug)]
+#[binrw]
+#[derive(Serialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
-#[br(little)]
+#[brw(little)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub struct VirtualStick {
     #[br(temp)]
+    #[bw(calc = pack_byte_field(u8::from(*vertical_control_mode), 0x30)
+        | pack_byte_field(u8::from(*roll_pitch_control_mode), 0xC0)
+        | pack_byte_field(u8::from(*yaw_control_mode), 0x08)
+        | pack_byte_field(u8::from(*coordinate_system), 0x06))]
     _bitpack1: u8,
     #[br(calc(VirtualStickVerticalControlMode::from(sub_byte_field(_bitpack1, 0x30))))]
+    #[bw(ignore)]
     pub vertical_control_mode: VirtualStickVerticalControlMode,
     #[br(calc(VirtualStickRollPitchControlMode::from(sub_byte_field(_bitpack1, 0xC0))))]
+    #[bw(ignore)]
     pub roll_pitch_control_mode: VirtualStickRollPitchControlMode,
     #[br(calc(VirtualStickYawControlMode::from(sub_byte_field(_bitpack1, 0x08))))]
+    #[bw(ignore)]
     pub yaw_control_mode: VirtualStickYawControlMode,
     #[br(calc(VirtualStickFlightCoordinateSystem::from(sub_byte_field(_bitpack1, 0x06))))]
+    #[bw(ignore)]
     pub coordinate_system: VirtualStickFlightCoordinateSystem,
 
     /// Aircraft Roll. left and right panning [-30, 30] degrees
@@ -32,13 +40,14 @@ pub struct VirtualStick {
     pub throttle: f32,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, Clone, Copy, Default)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub enum VirtualStickVerticalControlMode {
     /// Sets the virtual stick vertical control values to be a vertical velocity.
     /// Positive and negative vertical velocity is for the aircraft ascending and
     /// descending respectively. Maximum vertical velocity is defined as 4 m/s. Minimum
     /// vertical velocity is defined as -4 m/s.
+    #[default]
     Velocity,
     /// Sets the virtual stick vertical control values to be an altitude. Maximum
     /// position is defined as 500 m. Minimum position is defined as 0 m.
@@ -57,7 +66,17 @@ impl From<u8> for VirtualStickVerticalControlMode {
     }
 }
 
-#[derive(Serialize, Debug)]
+impl From<VirtualStickVerticalControlMode> for u8 {
+    fn from(value: VirtualStickVerticalControlMode) -> Self {
+        match value {
+            VirtualStickVerticalControlMode::Velocity => 0,
+            VirtualStickVerticalControlMode::Position => 1,
+            VirtualStickVerticalControlMode::Unknown(value) => value,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Copy, Default)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub enum VirtualStickRollPitchControlMode {
     /// Sets the roll and pitch values to be an angle relative to a level aircraft. In
@@ -70,6 +89,7 @@ pub enum VirtualStickRollPitchControlMode {
     /// and negative roll angle is the angle when the aircraft is moving east and west,
     /// respectively. Maximum angle is defined as 30 degrees. Minimum angle is defined
     /// as -30 degrees.
+    #[default]
     Angle,
     /// Sets the roll and pitch values to be a velocity. In the body coordinate system,
     /// positive and negative pitch velocity is for the aircraft moving towards the
@@ -96,13 +116,24 @@ impl From<u8> for VirtualStickRollPitchControlMode {
     }
 }
 
-#[derive(Serialize, Debug)]
+impl From<VirtualStickRollPitchControlMode> for u8 {
+    fn from(value: VirtualStickRollPitchControlMode) -> Self {
+        match value {
+            VirtualStickRollPitchControlMode::Angle => 0,
+            VirtualStickRollPitchControlMode::Velocity => 1,
+            VirtualStickRollPitchControlMode::Unknown(value) => value,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Copy, Default)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub enum VirtualStickYawControlMode {
     /// Sets the yaw values to be an angle relative to the north. Positive and negative
     /// yaw angle is for the aircraft rotating clockwise and counterclockwise,
     /// respectively. Maximum yaw angle is defined as 180 degrees. Minimum yaw angle is
     /// defined as -180 degrees.
+    #[default]
     Angle,
     /// Sets the yaw values to be an angular velocity. Positive and negative angular
     /// velocity is for the aircraft rotating clockwise and counterclockwise,
@@ -123,10 +154,21 @@ impl From<u8> for VirtualStickYawControlMode {
     }
 }
 
-#[derive(Serialize, Debug)]
+impl From<VirtualStickYawControlMode> for u8 {
+    fn from(value: VirtualStickYawControlMode) -> Self {
+        match value {
+            VirtualStickYawControlMode::Angle => 0,
+            VirtualStickYawControlMode::Velocity => 1,
+            VirtualStickYawControlMode::Unknown(value) => value,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Copy, Default)]
 #[cfg_attr(target_arch = "wasm32", derive(Tsify))]
 pub enum VirtualStickFlightCoordinateSystem {
     /// Ground coordinate system.
+    #[default]
     Ground,
     /// Body coordinate system.
     Body,
@@ -143,3 +185,13 @@ impl From<u8> for VirtualStickFlightCoordinateSystem {
         }
     }
 }
+
+impl From<VirtualStickFlightCoordinateSystem> for u8 {
+    fn from(value: VirtualStickFlightCoordinateSystem) -> Self {
+        match value {
+            VirtualStickFlightCoordinateSystem::Ground => 0,
+            VirtualStickFlightCoordinateSystem::Body => 1,
+            VirtualStickFlightCoordinateSystem::Unknown(value) => value,
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use binrw::binrw;
+use serde::Serialize;
+#[cfg(target_arch = "wasm32")]
+use tsify_next::Tsify;
+
+/// Vision warning message, `VisionWaringStringDataType` records, read like `AppWarn` records.
+#[binrw]
+#[derive(Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+#[brw(little)]
+#[br(import { length: u16 })]
+#[cfg_attr(target_arch = "wasm32", derive(Tsify))]
+pub struct VisionWarn {
+    #[br(count = usize::from(length), map = |s: Vec<u8>| String::from_utf8_lossy(&s).trim_end_matches('\0').to_string())]
+    #[bw(map = |s: &String| s.as_bytes().to_vec())]
+    pub message: String,
+}
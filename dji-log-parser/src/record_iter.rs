@@ -0,0 +1,600 @@
+use binrw::BinRead;
+use serde::Serialize;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::iter::FusedIterator;
+#[cfg(target_arch = "wasm32")]
+use tsify_next::Tsify;
+
+use crate::decoder::{lock_reader, read_record_content, SeekRead, SharedReader, XorDecoder};
+use crate::keychain::{FeaturePoint, Keychain};
+use crate::record::{Record, END_BYTE, RECORD_TYPES};
+use crate::report::{ParseIssue, ParseReport, RecoveryAction, RecoveryMode};
+use crate::{Error, Result};
+
+/// Maximum number of bytes scanned by the resync recovery mode to find the next plausible record
+const RESYNC_WINDOW: u64 = 1024 * 1024;
+/// Number of bytes read at once by the resync recovery mode, in addition to the lookahead
+const RESYNC_CHUNK: u64 = 64 * 1024;
+
+/// Lazy iterator over the records of a DJI log.
+///
+/// Records are decoded one at a time from the records section of the log, so memory usage
+/// does not grow with the size of the log. For encrypted logs (version 13 and later), the
+/// active keychain is rolled over each time a `KeyStorageRecover` record is encountered.
+///
+/// Records failing the AES padding check, usually because of a wrong keychain, are skipped by
+/// their declared length and yielded as `Record::Unknown` with their content left encrypted.
+/// They are counted in the decryption stats of the report.
+///
+/// By default, the iterator yields `Err` once if a record cannot be decoded and then stops.
+/// With `RecoveryMode::Resync`, it scans forward to the next plausible record and resumes
+/// decoding instead. Decoding failures and invalid data are collected in a `ParseReport`.
+///
+pub struct RecordIter<'a> {
+    reader: &'a SharedReader,
+    position: u64,
+    end_offset: u64,
+    version: u8,
+    keychains: VecDeque<Keychain>,
+    keychain: RefCell<Keychain>,
+    recovery: RecoveryMode,
+    report: ParseReport,
+    done: bool,
+}
+
+impl<'a> RecordIter<'a> {
+    pub(crate) fn new(
+        reader: &'a SharedReader,
+        start_offset: u64,
+        end_offset: u64,
+        version: u8,
+        mut keychains: VecDeque<Keychain>,
+    ) -> Self {
+        let keychain = RefCell::new(keychains.pop_front().unwrap_or(Keychain::empty()));
+
+        RecordIter {
+            reader,
+            position: start_offset,
+            end_offset,
+            version,
+            keychains,
+            keychain,
+            recovery: RecoveryMode::default(),
+            report: ParseReport::default(),
+            done: false,
+        }
+    }
+
+    /// Sets the behavior of the iterator when a record cannot be decoded.
+    ///
+    /// # Arguments
+    ///
+    /// * `recovery` - `RecoveryMode::Abort` to stop on the first failure (default), or
+    ///   `RecoveryMode::Resync` to resume decoding at the next plausible record.
+    ///
+    pub fn with_recovery(mut self, recovery: RecoveryMode) -> Self {
+        self.recovery = recovery;
+        self
+    }
+
+    /// Returns the diagnostics collected so far.
+    pub fn report(&self) -> &ParseReport {
+        &self.report
+    }
+
+    /// Consumes the iterator and returns the diagnostics collected so far.
+    pub fn into_report(self) -> ParseReport {
+        self.report
+    }
+
+    fn push_issue(
+        &mut self,
+        reader: &mut dyn SeekRead,
+        offset: u64,
+        error: String,
+        action: RecoveryAction,
+        resume_offset: Option<u64>,
+    ) {
+        let (record_type, length) = read_header(reader, offset, self.version);
+        self.report.issues.push(ParseIssue {
+            offset,
+            record_type,
+            length,
+            error,
+            action,
+            resume_offset,
+        });
+    }
+
+    /// Scans forward from the current position up to `limit` for the next plausible record.
+    ///
+    /// A candidate must have a known record type, a `0xFF` end byte at the declared length, and
+    /// be followed by another plausible record header. These checks are done on the raw bytes
+    /// read in chunks of `RESYNC_CHUNK`, and only candidates passing them are decoded, which must
+    /// succeed with sane values. At most `RESYNC_WINDOW` bytes are scanned.
+    /// The keychain state is only updated once a candidate is accepted.
+    ///
+    /// Returns the decoded record, its offset and the position following it.
+    fn resync(&mut self, mut reader: &mut dyn SeekRead, limit: u64) -> Option<(Record, u64, u64)> {
+        let start = self.position + 1;
+        let limit = limit.min(start.saturating_add(RESYNC_WINDOW));
+        if start >= limit {
+            return None;
+        }
+
+        // Candidates are checked against the following record, so keep enough bytes ahead
+        let max_length = if self.version <= 12 {
+            u8::MAX as u64
+        } else {
+            u16::MAX as u64
+        };
+        let lookahead = 2 * (self.header_size() + max_length + 1);
+        let mut buffer = Vec::new();
+        let mut buffer_start = start;
+
+        for offset in start..limit {
+            let buffer_end = buffer_start + buffer.len() as u64;
+            if offset + lookahead > buffer_end && buffer_end < self.end_offset {
+                // The buffer is shorter than requested when the file ends before `end_offset`
+                let drained = ((offset - buffer_start) as usize).min(buffer.len());
+                buffer.drain(..drained);
+                buffer_start += drained as u64;
+
+                let read_end = (offset + lookahead + RESYNC_CHUNK).min(self.end_offset);
+                let read_start = buffer_start + buffer.len() as u64;
+                reader.seek(SeekFrom::Start(read_start)).ok()?;
+                (&mut *reader)
+                    .take(read_end - read_start)
+                    .read_to_end(&mut buffer)
+                    .ok()?;
+                if buffer_start + buffer.len() as u64 <= offset {
+                    return None;
+                }
+            }
+
+            let index = (offset - buffer_start) as usize;
+            let next_index = match self.record_end(&buffer, index) {
+                Some(next_index) => next_index,
+                None => continue,
+            };
+            if buffer_start + (next_index as u64) < self.end_offset
+                && self.record_end(&buffer, next_index).is_none()
+            {
+                continue;
+            }
+
+            let keychain = RefCell::new(self.keychain.borrow().clone());
+
+            let candidate = reader
+                .seek(SeekFrom::Start(offset))
+                .map_err(binrw::Error::from)
+                .and_then(|_| {
+                    Record::read_args(
+                        &mut reader,
+                        binrw::args! {
+                            version: self.version,
+                            keychain: &keychain
+                        },
+                    )
+                })
+                .and_then(|record| Ok((record, reader.stream_position()?)));
+
+            if let Ok((record, position)) = candidate {
+                if is_plausible(&record) {
+                    self.keychain = keychain;
+                    return Some((record, offset, position));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Size of the record type and record length.
+    fn header_size(&self) -> u64 {
+        // Length is stored on one byte up to version 12
+        if self.version <= 12 {
+            2
+        } else {
+            3
+        }
+    }
+
+    /// Checks if a known record type, a length and a matching end byte are found at `index`.
+    ///
+    /// Returns the index following the end byte of the record.
+    fn record_end(&self, buffer: &[u8], index: usize) -> Option<usize> {
+        if !RECORD_TYPES.contains(buffer.get(index)?) {
+            return None;
+        }
+
+        let length = if self.version <= 12 {
+            *buffer.get(index + 1)? as usize
+        } else {
+            u16::from_le_bytes([*buffer.get(index + 1)?, *buffer.get(index + 2)?]) as usize
+        };
+
+        let end_index = index + self.header_size() as usize + length;
+        (*buffer.get(end_index)? == END_BYTE).then_some(end_index + 1)
+    }
+}
+
+impl RecordIter<'_> {
+    /// Decodes the next record, and returns it with its offset.
+    ///
+    /// The keychain is not rolled over on `KeyStorageRecover` records, see `rotate_keychain`.
+    fn decode_next(&mut self) -> Option<Result<(u64, Record)>> {
+        if self.done || self.position >= self.end_offset {
+            return None;
+        }
+        let offset = self.position;
+
+        // The reader is shared with the log, resume from the last known position
+        let mut reader = lock_reader(self.reader);
+
+        // decode record
+        let record = reader
+            .seek(SeekFrom::Start(self.position))
+            .map_err(binrw::Error::from)
+            .and_then(|_| {
+                Record::read_args(
+                    &mut *reader,
+                    binrw::args! {
+                        version: self.version,
+                        keychain: &self.keychain
+                    },
+                )
+            })
+            .and_then(|record| Ok((record, reader.stream_position()?)));
+
+        // A record failing to decrypt is skipped by its declared length, to keep the framing
+        // of the following records
+        let record = match record {
+            Ok((Record::Invalid(_), _)) | Err(_) => self
+                .read_undecrypted(&mut **reader, offset)
+                .map_or(record, Ok),
+            record => record,
+        };
+
+        let (offset, record) = match record {
+            Ok((Record::Invalid(data), position)) => {
+                let error = format!("Invalid record data ({} bytes)", data.len());
+
+                // Invalid data may hide valid records, look for one before the end of the data
+                let resynced = match self.recovery {
+                    RecoveryMode::Resync => self.resync(&mut **reader, position),
+                    RecoveryMode::Abort => None,
+                };
+
+                match resynced {
+                    Some((record, resync_offset, next_position)) => {
+                        self.push_issue(
+                            &mut **reader,
+                            self.position,
+                            error,
+                            RecoveryAction::Resynced,
+                            Some(resync_offset),
+                        );
+                        self.position = next_position;
+                        (resync_offset, record)
+                    }
+                    None => {
+                        self.push_issue(
+                            &mut **reader,
+                            self.position,
+                            error,
+                            RecoveryAction::Skipped,
+                            Some(position),
+                        );
+                        self.position = position;
+                        (offset, Record::Invalid(data))
+                    }
+                }
+            }
+            Ok((record, position)) => {
+                self.position = position;
+                (offset, record)
+            }
+            Err(e) => {
+                let resynced = match self.recovery {
+                    RecoveryMode::Resync => self.resync(&mut **reader, self.end_offset),
+                    RecoveryMode::Abort => None,
+                };
+
+                match resynced {
+                    Some((record, resync_offset, position)) => {
+                        self.push_issue(
+                            &mut **reader,
+                            self.position,
+                            e.to_string(),
+                            RecoveryAction::Resynced,
+                            Some(resync_offset),
+                        );
+                        self.position = position;
+                        (resync_offset, record)
+                    }
+                    None => {
+                        self.push_issue(
+                            &mut **reader,
+                            self.position,
+                            e.to_string(),
+                            RecoveryAction::Aborted,
+                            None,
+                        );
+                        self.report
+                            .add_decryption_stats(self.keychain.borrow_mut().take_stats());
+                        self.done = true;
+                        return Some(Err(e.into()));
+                    }
+                }
+            }
+        };
+
+        self.report
+            .add_decryption_stats(self.keychain.borrow_mut().take_stats());
+
+        Some(Ok((offset, record)))
+    }
+
+    /// Reads the record at `offset` as `Record::Unknown` if it failed the AES padding check.
+    ///
+    /// The content of the record is Xor decoded and left encrypted. The failure is counted in
+    /// the decryption stats of the report.
+    ///
+    /// Returns the record and the position following it.
+    fn read_undecrypted(&self, reader: &mut dyn SeekRead, offset: u64) -> Option<(Record, u64)> {
+        if !self
+            .keychain
+            .borrow()
+            .is_failed_record(offset + self.header_size())
+        {
+            return None;
+        }
+
+        let (record_type, length) = match read_header(reader, offset, self.version) {
+            (Some(record_type), Some(length)) => (record_type, length),
+            _ => return None,
+        };
+
+        // firstChar and lastChar are not part of the content
+        let mut data = vec![0u8; usize::from(length) + 1];
+        reader.read_exact(&mut data).ok()?;
+        if data.pop() != Some(END_BYTE) {
+            return None;
+        }
+
+        let mut content = vec![0u8; usize::from(length.checked_sub(2)?)];
+        XorDecoder::new(io::Cursor::new(data), record_type)
+            .and_then(|mut decoder| decoder.read_exact(&mut content))
+            .ok()?;
+
+        let position = offset + self.header_size() + u64::from(length) + 1;
+        Some((Record::Unknown(record_type, content), position))
+    }
+
+    /// Starts a new keychain after a `KeyStorageRecover` record.
+    fn rotate_keychain(&mut self, record: &Record) {
+        if let Record::KeyStorageRecover(_) = record {
+            self.keychain = RefCell::new(self.keychains.pop_front().unwrap_or(Keychain::empty()));
+        }
+    }
+}
+
+impl Iterator for RecordIter<'_> {
+    type Item = Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let record = self.decode_next()?.map(|(_, record)| record);
+
+        if let Ok(record) = &record {
+            self.rotate_keychain(record);
+        }
+
+        Some(record)
+    }
+}
+
+impl FusedIterator for RecordIter<'_> {}
+
+/// A record along with its position in the log file and its decoded bytes.
+///
+/// The decoded bytes are the record content once Xor decoded and AES decrypted, before being
+/// parsed. They can be compared across firmware versions to find the meaning of unknown fields.
+///
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(target_arch = "wasm32", derive(Tsify))]
+pub struct RawRecord {
+    /// Offset of the record in the log file
+    pub offset: u64,
+    /// Record type, `None` for JPEG images and invalid data which are not framed as records
+    pub record_type: Option<u8>,
+    /// Declared length of the record, `None` for JPEG images and invalid data
+    pub length: Option<u16>,
+    /// Feature point the record is encrypted with, `None` for JPEG images and invalid data
+    pub feature_point: Option<FeaturePoint>,
+    /// Record content without the record type, length and end byte, Xor decoded and AES
+    /// decrypted. Records failing the AES padding check are left encrypted. JPEG images and
+    /// invalid data are returned as they were read.
+    pub decrypted: Vec<u8>,
+    pub parsed: Record,
+}
+
+/// Lazy iterator over the records of a DJI log, with their offset and decoded bytes.
+///
+/// See `RecordIter` for the decoding and recovery behavior.
+///
+pub struct RawRecordIter<'a> {
+    records: RecordIter<'a>,
+}
+
+impl<'a> RawRecordIter<'a> {
+    pub(crate) fn new(records: RecordIter<'a>) -> Self {
+        RawRecordIter { records }
+    }
+
+    /// Sets the behavior of the iterator when a record cannot be decoded.
+    ///
+    /// See `RecordIter::with_recovery`.
+    pub fn with_recovery(mut self, recovery: RecoveryMode) -> Self {
+        self.records = self.records.with_recovery(recovery);
+        self
+    }
+
+    /// Returns the diagnostics collected so far.
+    pub fn report(&self) -> &ParseReport {
+        self.records.report()
+    }
+
+    /// Consumes the iterator and returns the diagnostics collected so far.
+    pub fn into_report(self) -> ParseReport {
+        self.records.into_report()
+    }
+
+    /// Reads the decoded content of the record at `offset`, with its type and declared length.
+    ///
+    /// AES encrypted records are decrypted again with the keychain entry they were decoded with.
+    fn read_raw(&self, offset: u64) -> Result<(u8, u16, Vec<u8>)> {
+        let version = self.records.version;
+        let mut reader = lock_reader(self.records.reader);
+
+        let (record_type, length) = match read_header(&mut **reader, offset, version) {
+            (Some(record_type), Some(length)) => (record_type, length),
+            _ => {
+                return Err(Error::Io(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    format!(
+                        "Unable to read the header of the record at offset {}",
+                        offset
+                    ),
+                )))
+            }
+        };
+
+        // The reader is positioned after the header by `read_header`
+        let content = read_record_content(
+            &mut **reader,
+            record_type,
+            version,
+            &self.records.keychain,
+            length,
+        )?;
+
+        Ok((record_type, length, content))
+    }
+}
+
+impl Iterator for RawRecordIter<'_> {
+    type Item = Result<RawRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (offset, record) = match self.records.decode_next()? {
+            Ok(entry) => entry,
+            Err(e) => return Some(Err(e)),
+        };
+
+        let header_size = self.records.header_size();
+        let raw_record = match &record {
+            Record::Unknown(record_type, data)
+                if self
+                    .records
+                    .keychain
+                    .borrow()
+                    .is_failed_record(offset + header_size) =>
+            {
+                // Left encrypted, see `RecordIter::read_undecrypted`
+                Ok(RawRecord {
+                    offset,
+                    record_type: Some(*record_type),
+                    length: Some(data.len() as u16 + 2),
+                    feature_point: Some(FeaturePoint::from_record_type(
+                        *record_type,
+                        self.records.version,
+                    )),
+                    decrypted: data.clone(),
+                    parsed: record,
+                })
+            }
+            Record::JPEG(data) | Record::Invalid(data) => Ok(RawRecord {
+                offset,
+                record_type: None,
+                length: None,
+                feature_point: None,
+                decrypted: data.clone(),
+                parsed: record,
+            }),
+            _ => {
+                // `KeyStorageRecover` records are read without keychain, rotate first so that the
+                // next records use the next keychain even if this record cannot be read again
+                self.records.rotate_keychain(&record);
+
+                let raw_record = self.read_raw(offset);
+                if let Err(e) = &raw_record {
+                    let mut reader = lock_reader(self.records.reader);
+                    self.records.push_issue(
+                        &mut **reader,
+                        offset,
+                        e.to_string(),
+                        RecoveryAction::Skipped,
+                        Some(self.records.position),
+                    );
+                }
+
+                raw_record.map(|(record_type, length, decrypted)| RawRecord {
+                    offset,
+                    record_type: Some(record_type),
+                    length: Some(length),
+                    feature_point: Some(FeaturePoint::from_record_type(
+                        record_type,
+                        self.records.version,
+                    )),
+                    decrypted,
+                    parsed: record,
+                })
+            }
+        };
+
+        Some(raw_record)
+    }
+}
+
+impl FusedIterator for RawRecordIter<'_> {}
+
+/// Reads the record type byte and the declared record length at the given offset.
+fn read_header(reader: &mut dyn SeekRead, offset: u64, version: u8) -> (Option<u8>, Option<u16>) {
+    if reader.seek(SeekFrom::Start(offset)).is_err() {
+        return (None, None);
+    }
+
+    let mut record_type = [0u8];
+    if reader.read_exact(&mut record_type).is_err() {
+        return (None, None);
+    }
+
+    // Length is stored on one byte up to version 12
+    let mut length = [0u8; 2];
+    let length_size = if version <= 12 { 1 } else { 2 };
+    let length = reader
+        .read_exact(&mut length[..length_size])
+        .ok()
+        .map(|_| u16::from_le_bytes(length));
+
+    (Some(record_type[0]), length)
+}
+
+/// Checks that a decoded record carries sane values.
+fn is_plausible(record: &Record) -> bool {
+    match record {
+        Record::Invalid(_) | Record::JPEG(_) => false,
+        Record::OSD(osd) => {
+            osd.latitude.is_finite()
+                && osd.longitude.is_finite()
+                && osd.latitude.abs() <= 90.0
+                && osd.longitude.abs() <= 180.0
+        }
+        // Custom records already assert a valid update timestamp
+        _ => true,
+    }
+}
@@ -0,0 +1,102 @@
+use serde::Serialize;
+use std::collections::HashMap;
+#[cfg(target_arch = "wasm32")]
+use tsify_next::Tsify;
+
+use crate::keychain::FeaturePoint;
+
+/// Diagnostics collected while decoding the records of a DJI log.
+///
+/// Each decoding failure and each invalid data blob encountered in the records section
+/// is listed as a `ParseIssue`, in file order. For logs version 13 and above, AES encrypted
+/// records are also counted per `FeaturePoint`.
+///
+#[derive(Serialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(target_arch = "wasm32", derive(Tsify))]
+pub struct ParseReport {
+    /// Issues encountered while decoding records
+    pub issues: Vec<ParseIssue>,
+    /// Records decrypted, or failing to decrypt, with each feature point of the keychains
+    pub decryption: HashMap<FeaturePoint, DecryptionStats>,
+}
+
+impl ParseReport {
+    /// Returns `true` if no issue was encountered while decoding records.
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Returns `true` if decoding stopped before the end of the records section.
+    pub fn is_aborted(&self) -> bool {
+        self.issues
+            .iter()
+            .any(|issue| issue.action == RecoveryAction::Aborted)
+    }
+
+    /// Returns the number of records that failed to decrypt, for all feature points.
+    pub fn decryption_failures(&self) -> usize {
+        self.decryption.values().map(|stats| stats.failed).sum()
+    }
+
+    pub(crate) fn add_decryption_stats(&mut self, stats: HashMap<FeaturePoint, DecryptionStats>) {
+        for (feature_point, stats) in stats {
+            let total = self.decryption.entry(feature_point).or_default();
+            total.decrypted += stats.decrypted;
+            total.failed += stats.failed;
+        }
+    }
+}
+
+/// Number of AES encrypted records of a feature point.
+#[derive(Serialize, Debug, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(target_arch = "wasm32", derive(Tsify))]
+pub struct DecryptionStats {
+    /// Records decrypted with a valid padding
+    pub decrypted: usize,
+    /// Records failing the Pkcs7 padding check, usually because of a wrong or stale keychain
+    pub failed: usize,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(target_arch = "wasm32", derive(Tsify))]
+pub struct ParseIssue {
+    /// Offset of the record in the log file in bytes
+    pub offset: u64,
+    /// Record type byte, if it could be read
+    #[cfg_attr(target_arch = "wasm32", tsify(optional))]
+    pub record_type: Option<u8>,
+    /// Declared record length in bytes, if it could be read
+    #[cfg_attr(target_arch = "wasm32", tsify(optional))]
+    pub length: Option<u16>,
+    /// Description of the failure
+    pub error: String,
+    /// Action taken by the parser
+    pub action: RecoveryAction,
+    /// Offset in bytes at which decoding resumed, if it did
+    #[cfg_attr(target_arch = "wasm32", tsify(optional))]
+    pub resume_offset: Option<u64>,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(target_arch = "wasm32", derive(Tsify))]
+pub enum RecoveryAction {
+    /// The data was skipped and decoding continued with the next record
+    Skipped,
+    /// Decoding continued at the next plausible record found further in the file
+    Resynced,
+    /// Decoding stopped
+    Aborted,
+}
+
+/// Behavior of the record decoding when a record cannot be decoded.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum RecoveryMode {
+    /// Stop decoding on the first failure
+    #[default]
+    Abort,
+    /// Scan forward for the next plausible record and resume decoding
+    Resync,
+}
@@ -0,0 +1,31 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+#[cfg(target_arch = "wasm32")]
+use tsify_next::Tsify;
+
+use crate::layout::auxiliary::Department;
+use crate::layout::details::{Details, ProductType};
+
+/// Metadata of a DJI log, read by `DJILog::probe` without decoding any record.
+///
+/// Only the Prefix and the Details, or the Auxiliary blocks for logs version 13 and above,
+/// are read. This is meant to index large archives of logs.
+///
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(target_arch = "wasm32", derive(Tsify))]
+pub struct LogSummary {
+    /// Log format version
+    pub version: u8,
+    pub product_type: ProductType,
+    #[cfg_attr(target_arch = "wasm32", tsify(type = "string"))]
+    pub start_time: DateTime<Utc>,
+    pub aircraft_sn: String,
+    /// App department the log was recorded with, for logs version 13 and above
+    #[cfg_attr(target_arch = "wasm32", tsify(type = "string | number | null"))]
+    pub department: Option<Department>,
+    /// Version of the Auxiliary blocks, for logs version 13 and above
+    pub auxiliary_version: Option<u16>,
+    /// Log Details. Contains record summary and general informations
+    pub details: Details,
+}
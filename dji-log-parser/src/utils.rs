@@ -129,6 +129,7 @@ pub fn read_u16(from_u8: bool) -> BinResult<u16> {
 /// # Examples
 ///
 /// ```
+/// # use dji_log_parser::utils::sub_byte_field;
 /// let byte = 0b10101100;
 /// let mask = 0b11100000;
 /// assert_eq!(sub_byte_field(byte, mask), 0b00000101);
@@ -166,6 +167,71 @@ pub fn sub_byte_field(byte: u8, mask: u8) -> u8 {
     byte
 }
 
+/// Places a value into the bits selected by a mask, the inverse of `sub_byte_field`.
+///
+/// The value is shifted from the least significant bit (LSB) position to the position
+/// of the first `1` bit of `mask`, and bits falling outside of `mask` are discarded.
+///
+/// # Examples
+///
+/// ```
+/// # use dji_log_parser::utils::pack_byte_field;
+/// let value = 0b00000101;
+/// let mask = 0b11100000;
+/// assert_eq!(pack_byte_field(value, mask), 0b10100000);
+/// ```
+///
+/// # Parameters
+///
+/// * `value`: The value to place in the byte, aligned on the LSB.
+/// * `mask`: A mask specifying the bits receiving the value.
+///
+/// # Returns
+///
+/// Returns an `u8` byte containing the value at the position of the mask. Bytes built from
+/// several fields can be combined with a bitwise OR.
+pub fn pack_byte_field(value: u8, mask: u8) -> u8 {
+    if mask == 0 {
+        return 0;
+    }
+    (value << mask.trailing_zeros()) & mask
+}
+
+/// Encodes a string into a fixed length, null padded, array of bytes.
+///
+/// # Parameters
+///
+/// - `value`: The string to encode.
+/// - `length`: The length of the output. Longer strings are truncated.
+///
+/// # Returns
+///
+/// A `Vec<u8>` of exactly `length` bytes.
+pub fn string_to_bytes(value: &str, length: usize) -> Vec<u8> {
+    let mut bytes = value.as_bytes().to_vec();
+    bytes.resize(length, 0);
+    bytes
+}
+
+/// Encodes a dotted version string (e.g. `"1.2.3"`) into an array of bytes, one per component.
+///
+/// # Parameters
+///
+/// - `value`: The version string to encode. Missing or invalid components are encoded as `0`.
+/// - `length`: The number of components of the output.
+///
+/// # Returns
+///
+/// A `Vec<u8>` of exactly `length` bytes.
+pub fn version_to_bytes(value: &str, length: usize) -> Vec<u8> {
+    let mut bytes: Vec<u8> = value
+        .split('.')
+        .map(|part| part.parse().unwrap_or_default())
+        .collect();
+    bytes.resize(length, 0);
+    bytes
+}
+
 /// Ensures that a given slice of bytes has a minimum specified length, padding it with zeros if necessary.
 ///
 /// # Parameters
@@ -185,6 +251,7 @@ pub fn sub_byte_field(byte: u8, mask: u8) -> u8 {
 /// Basic usage:
 ///
 /// ```
+/// # use dji_log_parser::utils::pad_with_zeros;
 /// let bytes = b"Hello, world!";
 /// let padded_bytes = pad_with_zeros(&bytes[..], 16);
 /// assert_eq!(padded_bytes, b"Hello, world!\x00\x00\x00");
@@ -0,0 +1,264 @@
+use binrw::BinWrite;
+use std::io::{Cursor, Seek, SeekFrom, Write};
+
+use crate::decoder::{xor_encode, KEY_STORAGE_RECOVER_TYPE};
+use crate::layout::auxiliary::AuxiliaryVersion;
+use crate::layout::details::Details;
+use crate::layout::prefix::{Prefix, INFO_SIZE_V12};
+use crate::record::{Record, END_BYTE};
+use crate::utils::pad_with_zeros;
+use crate::{Error, Result};
+
+/// Writes records into a DJI log file.
+///
+/// The prefix is written when the writer is created, records are streamed to the underlying
+/// writer as they are pushed, and the layout is completed by `finish`:
+/// - v1 -> v11: Details are appended after the records and the prefix is updated with their offset.
+/// - v12: Details are written right after the prefix, before the records.
+/// - v13 and above: Auxiliary blocks holding the details and the keychain version are written
+///   right after the prefix, before the records. Record lengths are written on two bytes.
+///
+/// Starting with v7, records are Xor encoded. AES encryption of logs version 13 and above
+/// requires keychains only DJI can issue, so their records are only Xor encoded, and the log
+/// must be read with an empty keychain list. These logs are created with `new_with_auxiliary`.
+///
+/// Records pushed with `write_record` are re-encoded from their parsed fields, which is not
+/// lossless: fields the parser skips or derives are written as zeros, like the camera shoot
+/// flags of `Custom` records, bits of unknown meaning and content following the known fields
+/// are dropped, and coordinates converted from radians may differ in the last bit. To copy
+/// records byte for byte, push the content of `RawRecord`s with `write_raw_record` instead.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::fs::File;
+/// # use dji_log_parser::{DJILog, DJILogWriter};
+/// # let parser = DJILog::from_bytes(std::fs::read("path/to/log.txt")?)?;
+/// let mut writer = DJILogWriter::new(File::create("path/to/output")?, parser.version, parser.details.clone())?;
+/// for record in parser.records(None)? {
+///     writer.write_record(&record)?;
+/// }
+/// writer.finish()?;
+/// # Ok::<(), dji_log_parser::Error>(())
+/// ```
+///
+pub struct DJILogWriter<W: Write + Seek> {
+    writer: W,
+    start: u64,
+    prefix: Prefix,
+    details: Details,
+    record_count: usize,
+}
+
+impl<W: Write + Seek> DJILogWriter<W> {
+    /// Creates a writer and writes the log prefix.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The output, written from its current position.
+    /// * `version` - The log format version, from 1 to 12.
+    /// * `details` - The log details, written with the layout of `version`.
+    ///
+    /// # Returns
+    ///
+    /// The writer, or `Error::UnsupportedVersion` if `version` cannot be written.
+    /// Versions 13 and above are written with `new_with_auxiliary`.
+    pub fn new(mut writer: W, version: u8, details: Details) -> Result<Self> {
+        if version == 0 || version >= 13 {
+            return Err(Error::UnsupportedVersion(version));
+        }
+
+        let start = writer.stream_position()?;
+        let prefix = Prefix::new(version);
+
+        if version == 12 {
+            // The detail offset points to the end of the records, only known once they are written
+            write_prefix(&mut writer, &prefix)?;
+            let details = encode_details(&details, version)?;
+            writer.write_all(&pad_with_zeros(&details, INFO_SIZE_V12 as usize))?;
+        } else {
+            // The detail offset is only known once all records are written
+            write_prefix(&mut writer, &prefix)?;
+        }
+
+        Ok(DJILogWriter {
+            writer,
+            start,
+            prefix,
+            details,
+            record_count: 0,
+        })
+    }
+
+    /// Creates a writer for logs version 13 and above, and writes the prefix and the Auxiliary
+    /// blocks.
+    ///
+    /// Records are not AES encrypted, see `DJILogWriter`. The Auxiliary Info block holds the
+    /// details without signature.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The output, written from its current position.
+    /// * `version` - The log format version, 13 or above.
+    /// * `details` - The log details, written in the Auxiliary Info block.
+    /// * `auxiliary` - The keychain version and department, written in the Auxiliary Version block.
+    ///
+    /// # Returns
+    ///
+    /// The writer, or `Error::UnsupportedVersion` if `version` is below 13.
+    pub fn new_with_auxiliary(
+        mut writer: W,
+        version: u8,
+        details: Details,
+        auxiliary: AuxiliaryVersion,
+    ) -> Result<Self> {
+        if version < 13 {
+            return Err(Error::UnsupportedVersion(version));
+        }
+
+        let start = writer.stream_position()?;
+        let mut prefix = Prefix::new(version);
+
+        // Records start right after the Auxiliary blocks
+        let blocks = encode_auxiliary(&details, version, auxiliary)?;
+        prefix.set_detail(prefix.size() + blocks.len() as u64, blocks.len() as u16);
+        write_prefix(&mut writer, &prefix)?;
+        writer.write_all(&blocks)?;
+
+        Ok(DJILogWriter {
+            writer,
+            start,
+            prefix,
+            details,
+            record_count: 0,
+        })
+    }
+
+    /// Returns the version of the log being written.
+    pub fn version(&self) -> u8 {
+        self.prefix.version
+    }
+
+    /// Appends a record, re-encoded from its parsed fields.
+    ///
+    /// JPEG images and invalid data are written as they were read, without record framing.
+    /// Other records are not copied byte for byte, see `DJILogWriter`.
+    ///
+    /// # Returns
+    ///
+    /// `Error::RecordTooLarge` if the encoded record does not fit in the record length, one byte
+    /// prior to version 13 and two bytes after.
+    pub fn write_record(&mut self, record: &Record) -> Result<()> {
+        let content = record.content(self.prefix.version)?;
+
+        match record.record_type() {
+            Some(record_type) => self.write_raw_record(record_type, &content),
+            None => Ok(self.writer.write_all(&content)?),
+        }
+    }
+
+    /// Appends a record from its type and its content bytes, before Xor encoding.
+    ///
+    /// # Arguments
+    ///
+    /// * `record_type` - The type of the record.
+    /// * `content` - The record content, as decoded by `Record::Unknown`.
+    ///
+    /// # Returns
+    ///
+    /// `Error::RecordTooLarge` if the encoded record does not fit in the record length, one byte
+    /// prior to version 13 and two bytes after.
+    pub fn write_raw_record(&mut self, record_type: u8, content: &[u8]) -> Result<()> {
+        let data = if self.prefix.version <= 6 || record_type == KEY_STORAGE_RECOVER_TYPE {
+            content.to_vec()
+        } else {
+            // Encoded records end with an extra byte, not part of the content
+            let mut content = content.to_vec();
+            content.push(0);
+            xor_encode(&content, self.record_count as u8, record_type)
+        };
+
+        let too_large = |_| Error::RecordTooLarge(record_type, data.len());
+        self.writer.write_all(&[record_type])?;
+        if self.prefix.version <= 12 {
+            let length = u8::try_from(data.len()).map_err(too_large)?;
+            self.writer.write_all(&[length])?;
+        } else {
+            let length = u16::try_from(data.len()).map_err(too_large)?;
+            self.writer.write_all(&length.to_le_bytes())?;
+        }
+        self.writer.write_all(&data)?;
+        self.writer.write_all(&[END_BYTE])?;
+
+        self.record_count += 1;
+        Ok(())
+    }
+
+    /// Completes the log layout and returns the underlying writer.
+    ///
+    /// For versions prior to 12, details are written after the records and the prefix is
+    /// updated with their offset and length. For version 12, the prefix is updated with the
+    /// offset of the end of the records.
+    pub fn finish(mut self) -> Result<W> {
+        if self.prefix.version <= 12 {
+            let end_offset = self.writer.stream_position()? - self.start;
+            if self.prefix.version < 12 {
+                let details = encode_details(&self.details, self.prefix.version)?;
+                self.writer.write_all(&details)?;
+                self.prefix.set_detail(end_offset, details.len() as u16);
+            } else {
+                self.prefix.set_detail(end_offset, INFO_SIZE_V12 as u16);
+            }
+
+            let end = self.writer.stream_position()?;
+            self.writer.seek(SeekFrom::Start(self.start))?;
+            write_prefix(&mut self.writer, &self.prefix)?;
+            self.writer.seek(SeekFrom::Start(end))?;
+        }
+
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+fn write_prefix<W: Write>(writer: &mut W, prefix: &Prefix) -> Result<()> {
+    let mut buffer = Cursor::new(Vec::new());
+    prefix.write(&mut buffer)?;
+
+    let mut bytes = buffer.into_inner();
+    bytes.truncate(prefix.size() as usize);
+    writer.write_all(&bytes)?;
+    Ok(())
+}
+
+fn encode_details(details: &Details, version: u8) -> Result<Vec<u8>> {
+    let mut buffer = Cursor::new(Vec::new());
+    details.write_args(&mut buffer, (version,))?;
+    Ok(buffer.into_inner())
+}
+
+/// Encodes the Auxiliary Info and Version blocks of logs version 13 and above.
+fn encode_auxiliary(
+    details: &Details,
+    version: u8,
+    auxiliary: AuxiliaryVersion,
+) -> Result<Vec<u8>> {
+    let details = encode_details(details, version)?;
+
+    let mut info = vec![0]; // version_data
+    info.extend((details.len() as u16).to_le_bytes());
+    info.extend(&details);
+    info.extend(0u16.to_le_bytes()); // No signature
+    let info = xor_encode(&info, 0, 0);
+
+    let mut blocks = vec![0];
+    blocks.extend((info.len() as u16).to_le_bytes());
+    blocks.extend(info);
+
+    blocks.push(1);
+    blocks.extend(3u16.to_le_bytes());
+    blocks.extend(auxiliary.version.to_le_bytes());
+    blocks.push(auxiliary.department.into());
+
+    Ok(blocks)
+}
@@ -0,0 +1,72 @@
+use std::io::{Cursor, Error, Read, Result, Seek, SeekFrom};
+
+use dji_log_fixtures::FlightScript;
+use dji_log_parser::anonymize::{Anonymizer, CoordinateTransform};
+use dji_log_parser::layout::details::ProductType;
+use dji_log_parser::DJILog;
+
+/// Reader failing to read past `fail_at`.
+struct FailingReader {
+    inner: Cursor<Vec<u8>>,
+    fail_at: u64,
+}
+
+impl Read for FailingReader {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if self.inner.position() >= self.fail_at {
+            return Err(Error::other("Read failure"));
+        }
+        let length = buf
+            .len()
+            .min((self.fail_at - self.inner.position()) as usize);
+        self.inner.read(&mut buf[..length])
+    }
+}
+
+impl Seek for FailingReader {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
+fn flight_log(version: u8) -> Vec<u8> {
+    FlightScript::new(ProductType::Mavic2, 46.7712, 8.6345)
+        .takeoff(30.0)
+        .leg(90.0, 200.0, 8.0)
+        .return_to_home(10.0)
+        .land()
+        .write(version)
+        .unwrap()
+}
+
+fn anonymizer() -> Anonymizer {
+    Anonymizer::new(CoordinateTransform::from_secret(b"__SECRET__"))
+}
+
+#[test]
+fn anonymized_log_keeps_records() {
+    let log = DJILog::from_bytes(flight_log(10)).unwrap();
+    let anonymized = DJILog::from_bytes(log.anonymize(&anonymizer()).unwrap()).unwrap();
+
+    assert!(anonymized.details.aircraft_sn.is_empty());
+    assert_eq!(
+        anonymized.records(None).unwrap().len(),
+        log.records(None).unwrap().len()
+    );
+}
+
+#[test]
+fn anonymize_fails_on_records_that_cannot_be_read() {
+    // Version 12 details precede the records, so only records fail to read
+    let bytes = flight_log(12);
+    let fail_at = DJILog::from_bytes(bytes.clone()).unwrap().records_offset() + 1000;
+    let log = DJILog::from_reader(FailingReader {
+        inner: Cursor::new(bytes),
+        fail_at,
+    })
+    .unwrap();
+
+    assert!(log.record_iter(None).unwrap().any(|record| record.is_err()));
+    assert!(log.anonymize(&anonymizer()).is_err());
+    assert!(log.anonymize_json(None, &anonymizer()).is_err());
+}
@@ -0,0 +1,192 @@
+use base64::engine::general_purpose::STANDARD as Base64Standard;
+use base64::Engine as _;
+use dji_log_fixtures::FlightScript;
+use dji_log_parser::keychain::{
+    EncodedKeychainFeaturePoint, FeaturePoint, KeychainFeaturePoint, KeychainsRequest,
+};
+use dji_log_parser::layout::details::ProductType;
+use dji_log_parser::record::Record;
+use dji_log_parser::{DJILog, Error};
+
+/// Type and decoded content of each record of a log.
+fn raw_records(
+    log: &DJILog,
+    keychains: Option<Vec<Vec<KeychainFeaturePoint>>>,
+) -> Vec<(Option<u8>, Vec<u8>)> {
+    log.raw_record_iter(keychains)
+        .unwrap()
+        .map(|raw_record| {
+            let raw_record = raw_record.unwrap();
+            (raw_record.record_type, raw_record.decrypted)
+        })
+        .collect()
+}
+
+fn keychains_request(keychains: &[&[(FeaturePoint, &str)]]) -> KeychainsRequest {
+    KeychainsRequest {
+        version: 1,
+        department: 3,
+        keychains: keychains
+            .iter()
+            .map(|keychain| {
+                keychain
+                    .iter()
+                    .map(
+                        |(feature_point, aes_ciphertext)| EncodedKeychainFeaturePoint {
+                            feature_point: *feature_point,
+                            aes_ciphertext: aes_ciphertext.to_string(),
+                        },
+                    )
+                    .collect()
+            })
+            .collect(),
+    }
+}
+
+fn flight(warning: &str) -> FlightScript {
+    FlightScript::new(ProductType::Mavic3, 46.7712, 8.6345)
+        .takeoff(30.0)
+        .leg(90.0, 200.0, 8.0)
+        .warning(warning)
+        .return_to_home(10.0)
+        .land()
+}
+
+#[test]
+fn encrypted_log_is_decoded_with_keychains() {
+    let (bytes, keychains) = flight("Strong wind.").write_encrypted(13).unwrap();
+    let log = DJILog::from_bytes(bytes).unwrap();
+    assert_eq!(log.version, 13);
+
+    let (records, report) = log.records_with_report(Some(keychains)).unwrap();
+    assert!(report.is_clean());
+    assert_eq!(report.decryption_failures(), 0);
+    assert!(records
+        .iter()
+        .any(|record| matches!(record, Record::OSD(_))));
+    assert!(records
+        .iter()
+        .any(|record| matches!(record, Record::AppWarn(warn) if warn.message == "Strong wind.")));
+}
+
+#[test]
+fn wrong_key_skips_records_by_declared_length() {
+    let (bytes, mut keychains) = flight("Strong wind.").write_encrypted(13).unwrap();
+    let log = DJILog::from_bytes(bytes).unwrap();
+    let expected = log.records(Some(keychains.clone())).unwrap();
+
+    for entry in keychains.iter_mut().flatten() {
+        if entry.feature_point == FeaturePoint::DJIFlyCustomFeature {
+            entry.aes_key = Base64Standard.encode([0x5A; 32]);
+        }
+    }
+
+    let (records, report) = log.records_with_report(Some(keychains)).unwrap();
+    assert!(report.is_clean());
+    assert_eq!(records.len(), expected.len());
+
+    // A wrong key still passes the padding check of about 1 record out of 256
+    let custom_stats = &report.decryption[&FeaturePoint::DJIFlyCustomFeature];
+    assert!(custom_stats.failed > 100 * custom_stats.decrypted);
+    assert_eq!(report.decryption[&FeaturePoint::BaseFeature].failed, 0);
+
+    for (record, expected) in records.iter().zip(&expected) {
+        match (expected, record) {
+            (Record::Custom(_), Record::Unknown(5, _) | Record::Custom(_)) => {}
+            (Record::AppWarn(_), Record::Unknown(10, _) | Record::AppWarn(_)) => {}
+            _ => assert_eq!(format!("{:?}", record), format!("{:?}", expected)),
+        }
+    }
+}
+
+#[test]
+fn decrypt_copies_records_as_version_12() {
+    let (bytes, keychains) = flight("Strong wind.").write_encrypted(13).unwrap();
+    let log = DJILog::from_bytes(bytes).unwrap();
+    let expected = raw_records(&log, Some(keychains.clone()));
+
+    let copy = DJILog::from_bytes(log.decrypt(Some(keychains)).unwrap()).unwrap();
+    assert_eq!(copy.version, 12);
+    assert_eq!(copy.details.aircraft_sn, log.details.aircraft_sn);
+    assert_eq!(raw_records(&copy, None), expected);
+}
+
+#[test]
+fn decrypt_keeps_version_of_large_records() {
+    let warning = "Strong wind. Fly with caution. ".repeat(10);
+    let (bytes, keychains) = flight(&warning).write_encrypted(13).unwrap();
+    let log = DJILog::from_bytes(bytes).unwrap();
+    let expected = raw_records(&log, Some(keychains.clone()));
+
+    let copy = DJILog::from_bytes(log.decrypt(Some(keychains)).unwrap()).unwrap();
+    assert_eq!(copy.version, 13);
+    assert_eq!(copy.details.aircraft_sn, log.details.aircraft_sn);
+    assert_eq!(raw_records(&copy, Some(Vec::new())), expected);
+
+    let records = copy.records(Some(Vec::new())).unwrap();
+    assert!(records
+        .iter()
+        .any(|record| matches!(record, Record::AppWarn(warn) if warn.message == warning)));
+}
+
+#[test]
+fn decrypt_copies_unencrypted_logs_byte_for_byte() {
+    for version in [1, 7, 12] {
+        let log = DJILog::from_bytes(flight("Strong wind.").write(version).unwrap()).unwrap();
+        let copy = DJILog::from_bytes(log.decrypt(None).unwrap()).unwrap();
+
+        assert_eq!(copy.version, version);
+        assert_eq!(raw_records(&copy, None), raw_records(&log, None));
+    }
+}
+
+#[test]
+fn decrypt_fails_with_wrong_keychains() {
+    let (bytes, mut keychains) = flight("Strong wind.").write_encrypted(13).unwrap();
+    for entry in keychains.iter_mut().flatten() {
+        entry.aes_key = Base64Standard.encode([0x5A; 32]);
+    }
+
+    let log = DJILog::from_bytes(bytes).unwrap();
+    assert!(matches!(
+        log.decrypt(Some(keychains)),
+        Err(Error::DecryptionFailed(failures)) if failures > 0
+    ));
+}
+
+#[test]
+fn fingerprint_identifies_keychain_ciphertexts() {
+    let request = keychains_request(&[&[
+        (FeaturePoint::BaseFeature, "AAAA"),
+        (FeaturePoint::VisionFeature, "BBBB"),
+    ]]);
+    let fingerprint = request.fingerprint();
+    assert_eq!(fingerprint.len(), 64);
+
+    // Request version and department are not part of the fingerprint
+    let mut other_department = request.clone();
+    other_department.version = 2;
+    other_department.department = 5;
+    assert_eq!(other_department.fingerprint(), fingerprint);
+
+    for other in [
+        keychains_request(&[&[
+            (FeaturePoint::BaseFeature, "AAAA"),
+            (FeaturePoint::VisionFeature, "BBBC"),
+        ]]),
+        keychains_request(&[&[
+            (FeaturePoint::BaseFeature, "AAAA"),
+            (FeaturePoint::WaypointFeature, "BBBB"),
+        ]]),
+        keychains_request(&[
+            &[(FeaturePoint::BaseFeature, "AAAA")],
+            &[(FeaturePoint::VisionFeature, "BBBB")],
+        ]),
+        keychains_request(&[&[
+            (FeaturePoint::BaseFeature, "AAAAB"),
+            (FeaturePoint::VisionFeature, "BBB"),
+        ]]),
+    ] {
+        assert_ne!(other.fingerprint(), fingerprint);
+    }
+}
@@ -0,0 +1,65 @@
+use dji_log_fixtures::{FlightScript, VERSIONS};
+use dji_log_parser::layout::details::ProductType;
+use dji_log_parser::DJILog;
+
+fn flight() -> FlightScript {
+    FlightScript::new(ProductType::MavicAir2, 46.7712, 8.6345)
+        .takeoff(30.0)
+        .leg(90.0, 200.0, 8.0)
+        .return_to_home(10.0)
+        .land()
+}
+
+#[test]
+fn details_are_read_at_version_offsets() {
+    let script = flight();
+    let expected = script.details();
+
+    for version in VERSIONS {
+        let bytes = script.write(version).unwrap();
+        let size = bytes.len() as u64;
+        let log = DJILog::from_bytes(bytes).unwrap();
+        assert_eq!(log.version, version);
+
+        // Records follow the prefix, and the Details block follows the records up to version 11
+        let prefix = log.prefix();
+        match version {
+            1..=5 => {
+                assert_eq!(log.records_offset(), 12, "version {}", version);
+                assert_eq!(log.records_end_offset(), prefix.detail_offset());
+                assert_eq!(prefix.detail_offset() + prefix.detail_length() as u64, size);
+            }
+            6..=11 => {
+                assert_eq!(log.records_offset(), 100, "version {}", version);
+                assert_eq!(log.records_end_offset(), prefix.detail_offset());
+                assert_eq!(prefix.detail_offset() + prefix.detail_length() as u64, size);
+            }
+            _ => {
+                assert_eq!(prefix.detail_offset(), 100);
+                assert_eq!(log.records_offset(), 100 + 436);
+                assert_eq!(log.records_end_offset(), size);
+            }
+        }
+
+        let details = &log.details;
+        assert_eq!(
+            details.start_time, expected.start_time,
+            "version {}",
+            version
+        );
+        assert_eq!(details.latitude, expected.latitude);
+        assert_eq!(details.longitude, expected.longitude);
+        assert_eq!(details.total_distance, expected.total_distance);
+        // Total time is stored in milliseconds
+        assert!((details.total_time - expected.total_time).abs() < 0.001);
+        assert_eq!(details.max_height, expected.max_height);
+        assert_eq!(details.record_line_count, expected.record_line_count);
+        assert_eq!(details.product_type, expected.product_type);
+        assert_eq!(details.aircraft_name, expected.aircraft_name);
+        assert_eq!(details.aircraft_sn, expected.aircraft_sn);
+        assert_eq!(details.camera_sn, expected.camera_sn);
+        assert_eq!(details.rc_sn, expected.rc_sn);
+        assert_eq!(details.battery_sn, expected.battery_sn);
+        assert_eq!(details.app_version, expected.app_version);
+    }
+}
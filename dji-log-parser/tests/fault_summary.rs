@@ -0,0 +1,21 @@
+use dji_log_fixtures::{FlightScript, VERSIONS};
+use dji_log_parser::layout::details::ProductType;
+use dji_log_parser::DJILog;
+
+#[test]
+fn fault_free_flight_has_no_fault() {
+    let script = FlightScript::new(ProductType::Mavic2, 46.7712, 8.6345)
+        .takeoff(30.0)
+        .leg(90.0, 200.0, 8.0)
+        .return_to_home(10.0)
+        .land();
+
+    for version in VERSIONS {
+        let log = DJILog::from_bytes(script.write(version).unwrap()).unwrap();
+        let summary = log.fault_summary(None).unwrap();
+
+        assert!(!summary.has_fault(), "version {}", version);
+        assert_eq!(summary.motor_blocked_count, 0);
+        assert!(summary.imu_init_fail_reasons.is_empty());
+    }
+}
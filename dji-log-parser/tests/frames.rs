@@ -0,0 +1,122 @@
+use dji_log_fixtures::{FlightScript, VERSIONS};
+use dji_log_parser::frame::{records_to_frames, FrameBuilder};
+use dji_log_parser::layout::details::ProductType;
+use dji_log_parser::record::osd::FlightMode;
+use dji_log_parser::record::Record;
+use dji_log_parser::DJILog;
+
+fn flight() -> FlightScript {
+    FlightScript::new(ProductType::Mavic2, 46.7712, 8.6345)
+        .takeoff(30.0)
+        .tip("Aircraft is taking off.")
+        .leg(90.0, 200.0, 8.0)
+        .warning("Strong wind. Fly with caution.")
+        .hover(5.0)
+        .return_to_home(10.0)
+        .land()
+}
+
+#[test]
+fn records_to_frames_builds_one_frame_per_osd_record() {
+    let script = flight();
+
+    for version in VERSIONS {
+        let records = script.records(version);
+        let osd_count = records
+            .iter()
+            .filter(|record| matches!(record, Record::OSD(_)))
+            .count();
+
+        // The frame of the last OSD record is only returned by `FrameBuilder::finish`
+        let frames = records_to_frames(script.records(version), script.details());
+        assert_eq!(frames.len(), osd_count - 1, "version {}", version);
+
+        let mut builder = FrameBuilder::new(&script.details());
+        let mut frames: Vec<_> = records
+            .into_iter()
+            .filter_map(|record| builder.push(record))
+            .collect();
+        frames.extend(builder.finish());
+        assert_eq!(frames.len(), osd_count, "version {}", version);
+    }
+}
+
+#[test]
+fn frames_of_written_logs_match_records_to_frames() {
+    let script = flight();
+
+    for version in VERSIONS {
+        let log = DJILog::from_bytes(script.write(version).unwrap()).unwrap();
+        let frames = log.frames(None).unwrap();
+        let expected = records_to_frames(script.records(version), script.details());
+
+        // Coordinates are stored in radians, heights and fly time in tenths
+        assert_eq!(frames.len(), expected.len(), "version {}", version);
+        for (frame, expected) in frames.iter().zip(&expected) {
+            assert!((frame.osd.latitude - expected.osd.latitude).abs() < 1e-9);
+            assert!((frame.osd.longitude - expected.osd.longitude).abs() < 1e-9);
+            assert!((frame.osd.height - expected.osd.height).abs() <= 0.1);
+            assert!((frame.osd.fly_time - expected.osd.fly_time).abs() <= 0.1);
+            assert_eq!(frame.osd.is_motor_on, expected.osd.is_motor_on);
+            assert_eq!(frame.battery.charge_level, expected.battery.charge_level);
+            assert_eq!(frame.app.tip, expected.app.tip);
+            assert_eq!(frame.app.warn, expected.app.warn);
+        }
+    }
+}
+
+#[test]
+fn frames_follow_the_flight_script() {
+    let script = flight();
+
+    for version in VERSIONS {
+        let frames = records_to_frames(script.records(version), script.details());
+        let first = frames.first().unwrap();
+        let last = frames.last().unwrap();
+
+        // On ground at home before takeoff and after landing
+        assert!(first.osd.is_on_ground, "version {}", version);
+        assert!(!first.osd.is_motor_on);
+        assert!(last.osd.is_on_ground);
+        assert!(!last.osd.is_motor_on);
+        assert!((first.osd.latitude - 46.7712).abs() < 1e-5);
+        assert!((first.osd.longitude - 8.6345).abs() < 1e-5);
+        assert!((last.osd.latitude - 46.7712).abs() < 1e-5);
+        assert!((last.osd.longitude - 8.6345).abs() < 1e-5);
+
+        // Flies east up to 200 meters from home at takeoff height
+        let max_longitude = frames
+            .iter()
+            .map(|frame| frame.osd.longitude)
+            .fold(f64::MIN, f64::max);
+        assert!(max_longitude > 8.6345 + 0.002);
+        let max_height = frames
+            .iter()
+            .map(|frame| frame.osd.height)
+            .fold(f32::MIN, f32::max);
+        assert!((max_height - 30.0).abs() < 0.5, "version {}", version);
+
+        assert!(frames
+            .iter()
+            .any(|frame| matches!(frame.osd.flyc_state, Some(FlightMode::GoHome))));
+
+        // Messages are set on the frame of their record only
+        assert_eq!(
+            frames
+                .iter()
+                .filter(|frame| frame.app.tip.contains("Aircraft is taking off."))
+                .count(),
+            1
+        );
+        assert_eq!(
+            frames
+                .iter()
+                .filter(|frame| frame.app.warn.contains("Strong wind. Fly with caution."))
+                .count(),
+            1
+        );
+
+        // Battery drains during the flight
+        assert!(last.battery.charge_level < first.battery.charge_level);
+    }
+}
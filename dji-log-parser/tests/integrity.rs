@@ -0,0 +1,140 @@
+use dji_log_fixtures::{FlightScript, VERSIONS};
+use dji_log_parser::layout::details::ProductType;
+use dji_log_parser::{DJILog, IntegrityCheckKind, IntegrityReport, IntegrityStatus};
+
+fn flight() -> FlightScript {
+    FlightScript::new(ProductType::Mavic2, 46.7712, 8.6345)
+        .takeoff(30.0)
+        .leg(90.0, 200.0, 8.0)
+        .warning("Strong wind. Fly with caution.")
+        .return_to_home(10.0)
+        .land()
+}
+
+fn status(report: &IntegrityReport, kind: IntegrityCheckKind) -> IntegrityStatus {
+    report.check(kind).unwrap().status
+}
+
+#[test]
+fn unaltered_logs_pass_structural_checks() {
+    for version in VERSIONS {
+        let log = DJILog::from_bytes(flight().write(version).unwrap()).unwrap();
+        let report = log.verify_integrity(None).unwrap();
+
+        assert_eq!(
+            status(&report, IntegrityCheckKind::Layout),
+            IntegrityStatus::Pass,
+            "version {}",
+            version
+        );
+        assert_eq!(
+            status(&report, IntegrityCheckKind::Framing),
+            IntegrityStatus::Pass
+        );
+        assert_eq!(
+            status(&report, IntegrityCheckKind::Records),
+            IntegrityStatus::Pass
+        );
+        assert_eq!(
+            status(&report, IntegrityCheckKind::DetailsChecksum),
+            IntegrityStatus::Unknown
+        );
+
+        // Authenticity is not verified, logs written without signature fail
+        if version < 13 {
+            assert_eq!(
+                report.status,
+                IntegrityStatus::Unknown,
+                "version {}",
+                version
+            );
+            assert_eq!(
+                status(&report, IntegrityCheckKind::Signature),
+                IntegrityStatus::Unknown
+            );
+        } else {
+            assert_eq!(report.status, IntegrityStatus::Fail, "version {}", version);
+            assert_eq!(
+                status(&report, IntegrityCheckKind::Signature),
+                IntegrityStatus::Fail
+            );
+        }
+    }
+}
+
+#[test]
+fn encrypted_logs_decode_with_keychains() {
+    let (bytes, keychains) = flight().write_encrypted(13).unwrap();
+    let log = DJILog::from_bytes(bytes).unwrap();
+
+    let report = log.verify_integrity(Some(keychains)).unwrap();
+    assert_eq!(
+        status(&report, IntegrityCheckKind::Records),
+        IntegrityStatus::Pass
+    );
+
+    // Framing is checked without decrypting records
+    let report = log.verify_integrity(None).unwrap();
+    assert_eq!(
+        status(&report, IntegrityCheckKind::Framing),
+        IntegrityStatus::Pass
+    );
+    assert_eq!(
+        status(&report, IntegrityCheckKind::Records),
+        IntegrityStatus::Unknown
+    );
+}
+
+#[test]
+fn altered_record_length_fails_framing() {
+    for version in [1, 7, 12] {
+        let mut bytes = flight().write(version).unwrap();
+        let log = DJILog::from_bytes(bytes.clone()).unwrap();
+
+        // Length of a record in the middle of the records section
+        let middle = (log.records_offset() + log.records_end_offset()) / 2;
+        let offset = log
+            .raw_record_iter(None)
+            .unwrap()
+            .map(|raw_record| raw_record.unwrap().offset)
+            .find(|offset| *offset >= middle)
+            .unwrap();
+        bytes[offset as usize + 1] = bytes[offset as usize + 1].wrapping_add(1);
+
+        let log = DJILog::from_bytes(bytes).unwrap();
+        let report = log.verify_integrity(None).unwrap();
+        assert_eq!(report.status, IntegrityStatus::Fail, "version {}", version);
+        assert_eq!(
+            status(&report, IntegrityCheckKind::Framing),
+            IntegrityStatus::Fail
+        );
+    }
+}
+
+#[test]
+fn truncated_details_fail_layout() {
+    let mut bytes = flight().write(11).unwrap();
+    bytes.truncate(bytes.len() - 100);
+
+    let log = DJILog::from_bytes(bytes).unwrap();
+    let report = log.verify_integrity(None).unwrap();
+    assert_eq!(report.status, IntegrityStatus::Fail);
+    assert_eq!(
+        status(&report, IntegrityCheckKind::Layout),
+        IntegrityStatus::Fail
+    );
+}
+
+#[test]
+fn overflowing_detail_offset_fails_layout() {
+    let mut bytes = flight().write(10).unwrap();
+    bytes[..8].copy_from_slice(&(u64::MAX - 10).to_le_bytes());
+
+    let log = DJILog::from_bytes(bytes).unwrap();
+    let report = log.verify_integrity(None).unwrap();
+    assert_eq!(report.status, IntegrityStatus::Fail);
+    assert_eq!(
+        status(&report, IntegrityCheckKind::Layout),
+        IntegrityStatus::Fail
+    );
+}
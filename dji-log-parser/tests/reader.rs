@@ -0,0 +1,145 @@
+use std::io::{Cursor, Error, Read, Result, Seek, SeekFrom};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+
+use dji_log_fixtures::FlightScript;
+use dji_log_parser::layout::details::ProductType;
+use dji_log_parser::{DJILog, RecoveryAction};
+
+/// Reader counting the bytes read from the underlying source.
+struct CountingReader {
+    inner: Cursor<Vec<u8>>,
+    bytes_read: Arc<AtomicU64>,
+}
+
+impl Read for CountingReader {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let bytes_read = self.inner.read(buf)?;
+        self.bytes_read
+            .fetch_add(bytes_read as u64, Ordering::Relaxed);
+        Ok(bytes_read)
+    }
+}
+
+impl Seek for CountingReader {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
+/// Reader failing to read bytes again once `forward_only` is set.
+struct ForwardOnlyReader {
+    inner: Cursor<Vec<u8>>,
+    furthest: u64,
+    forward_only: Arc<AtomicBool>,
+}
+
+impl Read for ForwardOnlyReader {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let position = self.inner.position();
+        if self.forward_only.load(Ordering::Relaxed) && position < self.furthest {
+            return Err(Error::other("Bytes read again"));
+        }
+        let bytes_read = self.inner.read(buf)?;
+        self.furthest = self.furthest.max(position + bytes_read as u64);
+        Ok(bytes_read)
+    }
+}
+
+impl Seek for ForwardOnlyReader {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
+fn flight_log(version: u8) -> Vec<u8> {
+    FlightScript::new(ProductType::Mavic2, 46.7712, 8.6345)
+        .takeoff(30.0)
+        .leg(90.0, 500.0, 8.0)
+        .return_to_home(10.0)
+        .land()
+        .write(version)
+        .unwrap()
+}
+
+#[test]
+fn log_is_send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<DJILog>();
+}
+
+#[test]
+fn from_reader_reads_records_once() {
+    for version in [1, 7, 12] {
+        let bytes = flight_log(version);
+        let size = bytes.len() as u64;
+        let bytes_read = Arc::new(AtomicU64::new(0));
+
+        let log = DJILog::from_reader(CountingReader {
+            inner: Cursor::new(bytes.clone()),
+            bytes_read: bytes_read.clone(),
+        })
+        .unwrap();
+        let records = log.records(None).unwrap();
+
+        assert_eq!(
+            records.len(),
+            DJILog::from_bytes(bytes)
+                .unwrap()
+                .records(None)
+                .unwrap()
+                .len()
+        );
+        // Header reads and a few buffer refills, not one refill per record
+        assert!(
+            bytes_read.load(Ordering::Relaxed) < 3 * size,
+            "v{}: {} bytes read for a {} bytes log",
+            version,
+            bytes_read.load(Ordering::Relaxed),
+            size
+        );
+    }
+}
+
+#[test]
+fn detail_offset_out_of_seek_range_reads_like_from_bytes() {
+    let mut bytes = flight_log(10);
+    for detail_offset in [1 << 63, u64::MAX - 10, u64::MAX] {
+        bytes[..8].copy_from_slice(&u64::to_le_bytes(detail_offset));
+        let expected = DJILog::from_bytes(bytes.clone()).unwrap();
+
+        // Details past the end of the file are read as zeros
+        let log = DJILog::from_reader(Cursor::new(bytes.clone())).unwrap();
+        assert_eq!(log.details.aircraft_sn, expected.details.aircraft_sn);
+        assert_eq!(
+            log.records(None).unwrap().len(),
+            expected.records(None).unwrap().len()
+        );
+        let summary = DJILog::probe(Cursor::new(bytes.clone())).unwrap();
+        assert_eq!(summary.version, 10);
+    }
+}
+
+#[test]
+fn raw_record_read_failures_are_reported() {
+    let forward_only = Arc::new(AtomicBool::new(false));
+    let log = DJILog::from_reader(ForwardOnlyReader {
+        inner: Cursor::new(flight_log(12)),
+        furthest: 0,
+        forward_only: forward_only.clone(),
+    })
+    .unwrap();
+    forward_only.store(true, Ordering::Relaxed);
+
+    // Records across the end of the buffer are read again from the reader, after being decoded
+    let mut raw_records = log.raw_record_iter(None).unwrap();
+    let errors = raw_records
+        .by_ref()
+        .filter(|raw_record| raw_record.is_err())
+        .count();
+    let report = raw_records.into_report();
+
+    assert!(errors > 0);
+    assert_eq!(report.issues.len(), errors);
+    assert_eq!(report.issues[0].action, RecoveryAction::Skipped);
+}
@@ -0,0 +1,152 @@
+use std::time::{Duration, Instant};
+
+use dji_log_fixtures::FlightScript;
+use dji_log_parser::layout::details::ProductType;
+use dji_log_parser::record::Record;
+use dji_log_parser::{DJILog, RecoveryAction, RecoveryMode};
+
+fn flight_log(version: u8, distance: f64) -> Vec<u8> {
+    FlightScript::new(ProductType::Mavic2, 46.7712, 8.6345)
+        .takeoff(30.0)
+        .leg(90.0, distance, 8.0)
+        .return_to_home(10.0)
+        .land()
+        .write(version)
+        .unwrap()
+}
+
+fn next_random(seed: &mut u64) -> u64 {
+    *seed = seed
+        .wrapping_mul(6364136223846793005)
+        .wrapping_add(1442695040888963407);
+    *seed >> 17
+}
+
+/// Inverts `count` bytes of the records section at pseudo-random offsets.
+fn corrupt(bytes: &mut [u8], count: usize, mut seed: u64) {
+    let log = DJILog::from_bytes(bytes.to_vec()).unwrap();
+    let start = log.records_offset() as usize;
+    let end = log.records_end_offset() as usize;
+
+    for _ in 0..count {
+        let offset = start + next_random(&mut seed) as usize % (end - start);
+        bytes[offset] = !bytes[offset];
+    }
+}
+
+fn osd_count(log: &DJILog, recovery: RecoveryMode) -> usize {
+    log.record_iter(None)
+        .unwrap()
+        .with_recovery(recovery)
+        .filter_map(Result::ok)
+        .filter(|record| matches!(record, Record::OSD(_)))
+        .count()
+}
+
+#[test]
+fn resync_recovers_records_after_corruption() {
+    for version in [1, 7, 12] {
+        let mut bytes = flight_log(version, 500.0);
+        let expected = osd_count(
+            &DJILog::from_bytes(bytes.clone()).unwrap(),
+            RecoveryMode::Abort,
+        );
+
+        corrupt(&mut bytes, 20, version as u64);
+        let log = DJILog::from_bytes(bytes).unwrap();
+        let recovered = osd_count(&log, RecoveryMode::Resync);
+
+        assert!(
+            recovered > expected * 9 / 10,
+            "v{}: {} of {} OSD records recovered",
+            version,
+            recovered,
+            expected
+        );
+    }
+}
+
+#[test]
+fn resync_is_bounded_in_time() {
+    for version in 1..=12 {
+        let mut bytes = flight_log(version, 500.0);
+        corrupt(&mut bytes, 200, 42 + version as u64);
+
+        let started = Instant::now();
+        let log = DJILog::from_bytes(bytes).unwrap();
+        let mut records = log
+            .record_iter(None)
+            .unwrap()
+            .with_recovery(RecoveryMode::Resync);
+        records.by_ref().for_each(drop);
+        let report = records.into_report();
+        let elapsed = started.elapsed();
+
+        assert!(
+            elapsed < Duration::from_secs(5),
+            "v{}: resync took {:?}",
+            version,
+            elapsed
+        );
+        assert!(!report.is_aborted(), "v{}: {:?}", version, report.issues);
+        assert!(report
+            .issues
+            .iter()
+            .any(|issue| issue.action == RecoveryAction::Resynced));
+    }
+}
+
+#[test]
+fn resync_skips_large_garbage_block_in_time() {
+    for version in [1, 12] {
+        let mut bytes = flight_log(version, 10000.0);
+        let expected = osd_count(
+            &DJILog::from_bytes(bytes.clone()).unwrap(),
+            RecoveryMode::Abort,
+        );
+
+        // Fill 512 KB from the middle of the records section with random bytes
+        let log = DJILog::from_bytes(bytes.clone()).unwrap();
+        let start = (log.records_offset() + log.records_end_offset()) as usize / 2;
+        let mut seed = version as u64;
+        for byte in &mut bytes[start..start + 512 * 1024] {
+            *byte = next_random(&mut seed) as u8;
+        }
+
+        let started = Instant::now();
+        let log = DJILog::from_bytes(bytes).unwrap();
+        let recovered = osd_count(&log, RecoveryMode::Resync);
+        let elapsed = started.elapsed();
+
+        assert!(
+            elapsed < Duration::from_secs(5),
+            "v{}: resync took {:?}",
+            version,
+            elapsed
+        );
+        // Records of the second half, after the garbage block, are recovered
+        assert!(
+            recovered > expected / 3,
+            "v{}: {} of {} OSD records recovered",
+            version,
+            recovered,
+            expected
+        );
+    }
+}
+
+#[test]
+fn resync_stops_at_the_end_of_the_file() {
+    let mut bytes = flight_log(10, 200.0);
+    corrupt(&mut bytes, 20, 7);
+    // Records section declared past the end of the file
+    bytes[..8].copy_from_slice(&(u64::MAX - 10).to_le_bytes());
+
+    let log = DJILog::from_bytes(bytes).unwrap();
+    let mut records = log
+        .record_iter(None)
+        .unwrap()
+        .with_recovery(RecoveryMode::Resync);
+    assert!(records.by_ref().count() > 0);
+    assert!(!records.report().is_clean());
+}
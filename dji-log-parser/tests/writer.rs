@@ -0,0 +1,118 @@
+use std::io::Cursor;
+
+use dji_log_fixtures::FlightScript;
+use dji_log_parser::layout::details::ProductType;
+use dji_log_parser::record::app_serious_warn::AppSeriousWarn;
+use dji_log_parser::record::app_tip::AppTip;
+use dji_log_parser::record::app_warn::AppWarn;
+use dji_log_parser::record::vision_warn::VisionWarn;
+use dji_log_parser::record::Record;
+use dji_log_parser::{DJILog, DJILogWriter};
+
+const MESSAGE: &str = "Strong wind. Fly with caution.";
+
+fn write_log(version: u8, records: &[Record]) -> Vec<u8> {
+    let details = FlightScript::new(ProductType::Mavic2, 46.7712, 8.6345).details();
+    let mut writer = DJILogWriter::new(Cursor::new(Vec::new()), version, details).unwrap();
+    for record in records {
+        writer.write_record(record).unwrap();
+    }
+    writer.finish().unwrap().into_inner()
+}
+
+fn string_records() -> Vec<Record> {
+    vec![
+        Record::AppTip(AppTip {
+            message: MESSAGE.to_owned(),
+        }),
+        Record::AppWarn(AppWarn {
+            message: MESSAGE.to_owned(),
+        }),
+        Record::AppSeriousWarn(AppSeriousWarn {
+            message: MESSAGE.to_owned(),
+        }),
+        Record::VisionWarn(VisionWarn {
+            message: MESSAGE.to_owned(),
+        }),
+    ]
+}
+
+fn message(record: &Record) -> Option<&str> {
+    match record {
+        Record::AppTip(tip) => Some(&tip.message),
+        Record::AppWarn(warn) => Some(&warn.message),
+        Record::AppSeriousWarn(warn) => Some(&warn.message),
+        Record::VisionWarn(warn) => Some(&warn.message),
+        _ => None,
+    }
+}
+
+#[test]
+fn string_records_round_trip() {
+    for version in 1..=12 {
+        for record in string_records() {
+            // A second record checks the framing of the first one
+            let bytes = write_log(version, &[record, string_records().remove(0)]);
+            let (records, report) = DJILog::from_bytes(bytes)
+                .unwrap()
+                .records_with_report(None)
+                .unwrap();
+
+            assert!(report.is_clean(), "v{}: {:?}", version, report.issues);
+            assert_eq!(records.len(), 2, "v{}: {:?}", version, records);
+            for record in &records {
+                assert_eq!(message(record), Some(MESSAGE), "v{}: {:?}", version, record);
+            }
+        }
+    }
+}
+
+#[test]
+fn fixture_records_round_trip() {
+    let script = FlightScript::new(ProductType::Mavic2, 46.7712, 8.6345)
+        .takeoff(30.0)
+        .leg(90.0, 200.0, 8.0)
+        .warning(MESSAGE)
+        .tip("Return to home started.")
+        .return_to_home(10.0)
+        .land();
+
+    for version in 1..=12 {
+        let records = DJILog::from_bytes(script.write(version).unwrap())
+            .unwrap()
+            .records(None)
+            .unwrap();
+        let (rewritten, report) = DJILog::from_bytes(write_log(version, &records))
+            .unwrap()
+            .records_with_report(None)
+            .unwrap();
+
+        assert!(report.is_clean(), "v{}: {:?}", version, report.issues);
+        assert_eq!(records.len(), rewritten.len(), "v{}", version);
+        for (record, rewritten) in records.iter().zip(&rewritten) {
+            assert_eq!(
+                format!("{:?}", record),
+                format!("{:?}", rewritten),
+                "v{}",
+                version
+            );
+        }
+    }
+}
+
+#[test]
+fn prefix_points_to_the_end_of_the_records() {
+    for version in [5, 11, 12] {
+        let bytes = write_log(version, &string_records());
+        let detail_offset = u64::from_le_bytes(bytes[..8].try_into().unwrap());
+        let detail_length = u16::from_le_bytes(bytes[8..10].try_into().unwrap()) as u64;
+
+        // Details follow the records prior to version 12, and precede them in version 12
+        if version < 12 {
+            assert_eq!(detail_offset + detail_length, bytes.len() as u64);
+        } else {
+            assert_eq!(detail_offset, bytes.len() as u64);
+            assert_eq!(detail_length, 436);
+        }
+    }
+}